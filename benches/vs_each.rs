@@ -1,9 +1,13 @@
+#![allow(dead_code)]
+
 use criterion::*;
 use std::iter::FromIterator;
-use voluntary_servitude::{VS, vs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{env, sync::Arc, thread};
+use voluntary_servitude::{vs, VS};
 
 fn vs_new(c: &mut Criterion) {
-    c.bench_function("vs_new", move |b| b.iter(|| VS::<()>::new()));
+    c.bench_function("vs_new", move |b| b.iter(VS::<()>::new));
 }
 
 fn vs_append(c: &mut Criterion) {
@@ -11,11 +15,160 @@ fn vs_append(c: &mut Criterion) {
     c.bench_function("vs_append", move |b| b.iter(|| vs.append(10)));
 }
 
+fn vs_append_many(c: &mut Criterion) {
+    c.bench_function("vs_append_many", move |b| {
+        b.iter(|| {
+            let vs: VS<u16> = VS::default();
+            for i in 0..1000 {
+                vs.append(i);
+            }
+            vs
+        })
+    });
+}
+
+fn vs_extend_from_slice(c: &mut Criterion) {
+    let slice: Vec<u16> = (0..1000).collect();
+    c.bench_function("vs_extend_from_slice", move |b| {
+        b.iter(|| {
+            let vs: VS<u16> = VS::default();
+            vs.extend_from_slice(&slice);
+            vs
+        })
+    });
+}
+
+fn vs_batch(c: &mut Criterion) {
+    c.bench_function("vs_batch", move |b| {
+        b.iter(|| {
+            let vs: VS<u16> = VS::default();
+            let mut batch = vs.batch();
+            for i in 0..1000 {
+                batch.push(i);
+            }
+            batch.commit();
+            vs
+        })
+    });
+}
+
+/// Appends per thread in each contended iteration
+const CONTENDED_APPENDS: usize = 10_000;
+
+/// Thread counts to benchmark, configurable with a comma separated `VS_BENCH_THREADS` (like `1,2,4,8`)
+fn thread_counts() -> Vec<usize> {
+    env::var("VS_BENCH_THREADS")
+        .ok()
+        .map(|var| {
+            var.split(',')
+                .filter_map(|n| n.trim().parse().ok())
+                .collect()
+        })
+        .filter(|counts: &Vec<usize>| !counts.is_empty())
+        .unwrap_or_else(|| vec![1, 2, 4, 8])
+}
+
+/// Spawns `threads` appenders on a new list (and `readers` iterating it until they finish)
+fn append_contended(threads: usize, readers: usize) -> Arc<VS<usize>> {
+    let (vs, done) = (Arc::new(VS::default()), Arc::new(AtomicBool::new(false)));
+    let readers: Vec<_> = (0..readers)
+        .map(|_| {
+            let (vs, done) = (Arc::clone(&vs), Arc::clone(&done));
+            thread::spawn(move || {
+                while !done.load(Ordering::Relaxed) {
+                    let _ = black_box(vs.iter().sum::<usize>());
+                }
+            })
+        })
+        .collect();
+    let appenders: Vec<_> = (0..threads)
+        .map(|_| {
+            let vs = Arc::clone(&vs);
+            thread::spawn(move || (0..CONTENDED_APPENDS).for_each(|i| vs.append(i)))
+        })
+        .collect();
+
+    appenders.into_iter().for_each(|t| t.join().unwrap());
+    done.store(true, Ordering::Relaxed);
+    readers.into_iter().for_each(|t| t.join().unwrap());
+    vs
+}
+
+fn vs_append_contended(c: &mut Criterion) {
+    let bench = ParameterizedBenchmark::new(
+        "threads",
+        |b, &threads| b.iter_with_large_drop(|| append_contended(threads, 0)),
+        thread_counts(),
+    );
+    let bench =
+        bench.throughput(|&threads| Throughput::Elements((threads * CONTENDED_APPENDS) as u32));
+    c.bench("vs_append_contended", bench.sample_size(10));
+}
+
+/// Every appender thread has a reader thread iterating the list meanwhile, only appends are counted
+fn vs_append_iter_contended(c: &mut Criterion) {
+    let bench = ParameterizedBenchmark::new(
+        "threads",
+        |b, &threads| b.iter_with_large_drop(|| append_contended(threads, threads)),
+        thread_counts(),
+    );
+    let bench =
+        bench.throughput(|&threads| Throughput::Elements((threads * CONTENDED_APPENDS) as u32));
+    c.bench("vs_append_iter_contended", bench.sample_size(10));
+}
+
+/// List where every value appears twice (the duplicates aren't consecutive)
+fn duplicated() -> VS<u32> {
+    (0..2000).map(|i| i % 1000).collect()
+}
+
+/// Compares the `O(n²)` `unique` with the `HashSet` based `unique_hashed`
+fn vs_unique(c: &mut Criterion) {
+    let bench = Benchmark::new("unique", |b| {
+        b.iter_with_setup(duplicated, |vs| {
+            vs.unique();
+            vs
+        })
+    });
+    let bench = bench.with_function("unique_hashed", |b| {
+        b.iter_with_setup(duplicated, |vs| {
+            vs.unique_hashed();
+            vs
+        })
+    });
+    c.bench("vs_unique", bench.sample_size(20));
+}
+
 fn vs_iter(c: &mut Criterion) {
     let vs = vs![10u8; 1000];
     c.bench_function("vs_iter", move |b| b.iter(|| vs.iter()));
 }
 
+/// Interleaves appends between lists so each chain is scattered in memory
+fn fragmented() -> VS<u64> {
+    let (vs, noise) = (VS::default(), VS::default());
+    for i in 0..1000 {
+        vs.append(i);
+        noise.append([i; 8]);
+    }
+    vs
+}
+
+fn vs_iter_fragmented(c: &mut Criterion) {
+    let vs = fragmented();
+    c.bench_function("vs_iter_fragmented", move |b| {
+        b.iter(|| vs.iter().sum::<u64>())
+    });
+}
+
+fn vs_iter_consolidated(c: &mut Criterion) {
+    let vs = fragmented();
+    vs.consolidate();
+    c.bench_function("vs_iter_consolidated", move |b| {
+        b.iter(|| vs.iter().sum::<u64>())
+    });
+}
+
 fn vs_len(c: &mut Criterion) {
     let vs = vs![10u8; 1000];
     c.bench_function("vs_len", move |b| b.iter(|| vs.len()));
@@ -41,7 +194,9 @@ fn vs_swap(c: &mut Criterion) {
 
 fn vs_extend(c: &mut Criterion) {
     let vs = vs![3, 2];
-    c.bench_function("vs_extend", move |b| b.iter(|| vs.extend(vec![1, 0, -1, -2, -3, -4])));
+    c.bench_function("vs_extend", move |b| {
+        b.iter(|| vs.extend(vec![1, 0, -1, -2, -3, -4]))
+    });
 }
 
 fn vs_from_iter(c: &mut Criterion) {
@@ -52,7 +207,7 @@ fn vs_from_iter(c: &mut Criterion) {
 }
 
 fn vec_new(c: &mut Criterion) {
-    c.bench_function("vec_new", move |b| b.iter(|| Vec::<()>::new()));
+    c.bench_function("vec_new", move |b| b.iter(Vec::<()>::new));
 }
 
 fn vec_append(c: &mut Criterion) {
@@ -81,16 +236,38 @@ fn vec_clear(c: &mut Criterion) {
 
 fn vec_extend(c: &mut Criterion) {
     let mut vec = vec![3, 2];
-    c.bench_function("vec_extend", move |b| b.iter(|| vec.extend(vec![1, 0, -1, -2, -3, -4])));
+    c.bench_function("vec_extend", move |b| {
+        b.iter(|| vec.extend(vec![1, 0, -1, -2, -3, -4]))
+    });
 }
 
 fn vec_from_iter(c: &mut Criterion) {
-    let vec = vec![3, 2];
+    let vec = [3, 2];
     c.bench_function("vec_from_iter", move |b| {
         b.iter(|| Vec::from_iter(vec.iter().cloned()))
     });
 }
 
-criterion_group!(vs, vs_new, vs_append, vs_iter, vs_len, vs_is_empty, vs_clear, vs_empty, vs_swap, vs_extend, vs_from_iter);
+criterion_group!(
+    vs,
+    vs_new,
+    vs_append,
+    vs_append_many,
+    vs_append_contended,
+    vs_append_iter_contended,
+    vs_extend_from_slice,
+    vs_batch,
+    vs_iter,
+    vs_iter_fragmented,
+    vs_iter_consolidated,
+    vs_len,
+    vs_is_empty,
+    vs_clear,
+    vs_empty,
+    vs_swap,
+    vs_extend,
+    vs_from_iter,
+    vs_unique
+);
 //criterion_group!(vec, vec_new, vec_append, vec_iter, vec_len, vec_is_empty, vec_clear, vec_extend, vec_from_iter);
-criterion_main!(vs);//, vec);
+criterion_main!(vs); //, vec);
@@ -1,5 +1,5 @@
-use voluntary_servitude::vs;
 use std::{sync::Arc, thread::spawn};
+use voluntary_servitude::vs;
 
 const CONSUMERS: usize = 8;
 const PRODUCERS: usize = 4;
@@ -11,7 +11,8 @@
 use crate::prelude::*;
 use std::fmt::{self, Debug, Formatter, Pointer};
 use std::ptr::{null_mut, NonNull};
-use std::{marker::PhantomData, mem::drop, sync::atomic::AtomicPtr, sync::atomic::Ordering};
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::{marker::PhantomData, mem::drop, mem::forget, process::abort};
 
 /// Atomic `Box<T>`
 ///
@@ -21,6 +22,8 @@ use std::{marker::PhantomData, mem::drop, sync::atomic::AtomicPtr, sync::atomic:
 ///
 /// [`FillOnceAtomicOption`] provides a API that enables access to the reference, but only enables `try_store` to write to it
 ///
+/// It doesn't implement `Clone`, reading the value would require taking it out and `Atomic` can't be left empty
+///
 /// [`FillOnceAtomicOption`]: ./struct.FillOnceAtomicOption.html
 pub struct Atomic<T>(AtomicPtr<T>, PhantomData<Option<Box<T>>>);
 
@@ -102,6 +105,70 @@ impl<T> Atomic<T> {
         unsafe { self.inner_swap(new.into().into_ptr(), order) }
     }
 
+    /// Stores `new` if the current pointer is `current`, returning the old value (like `AtomicPtr::compare_exchange`)
+    ///
+    /// On failure the actual pointer is returned with `new` (so it can be retried without reallocating)
+    ///
+    /// Only pointers are compared, if the value at `current` was freed and its address reused it will still match (ABA)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::Atomic;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering::{Acquire, Relaxed};
+    /// let atomic = Atomic::from(1);
+    /// let current = atomic.get_raw(Relaxed);
+    /// let old = atomic.compare_exchange(current, Box::new(2), Acquire, Relaxed).unwrap();
+    /// assert_eq!(*old, 1);
+    ///
+    /// let (actual, new) = atomic.compare_exchange(current, Box::new(3), Acquire, Relaxed).unwrap_err();
+    /// assert_eq!((actual, *new), (atomic.get_raw(Relaxed), 3));
+    /// ```
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: *mut T,
+        new: Box<T>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Box<T>, (*mut T, Box<T>)> {
+        trace!("compare_exchange({:p})", current);
+        let new = new.into_ptr();
+        match self.0.compare_exchange(current, new, success, failure) {
+            // The exchange took the old pointer out, so we own it (it's never `null`)
+            Ok(old) => Ok(unsafe { Box::from_raw(old) }),
+            // `new` wasn't stored, so we still own it
+            Err(actual) => Err((actual, unsafe { Box::from_raw(new) })),
+        }
+    }
+
+    /// Replaces value with the one returned by `f`, that receives the old value
+    ///
+    /// It's a single-writer helper: it needs exclusive access, since `Atomic` can't be left empty while `f` runs (use `swap` to replace a shared value)
+    ///
+    /// # Panics
+    ///
+    /// Aborts the process if `f` panics, since `Atomic` can't be left empty
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::Atomic;
+    /// # env_logger::init();
+    /// let mut atomic = Atomic::from(10);
+    /// atomic.replace_with(|old| Box::new(*old * 2));
+    /// assert_eq!(*atomic.into_inner(), 20);
+    /// ```
+    #[inline]
+    pub fn replace_with<F>(&mut self, f: F)
+    where
+        F: FnOnce(Box<T>) -> Box<T>,
+    {
+        trace!("replace_with()");
+        let ptr = self.0.get_mut();
+        let abort = AbortOnUnwind;
+        // We have exclusive access, so we own the value until the new one is stored
+        *ptr = f(unsafe { Box::from_raw(*ptr) }).into_ptr();
+        forget(abort);
+    }
+
     /// Converts itself into a `Box<T>`
     ///
     /// ```rust
@@ -118,6 +185,24 @@ impl<T> Atomic<T> {
         unsafe { self.inner_swap(null_mut(), Ordering::Relaxed) }
     }
 
+    /// Converts itself into a raw pointer without dropping the value (the caller takes ownership, like `Box::into_raw`)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::Atomic;
+    /// # env_logger::init();
+    /// let ptr = Atomic::from(10).into_raw();
+    /// let ten = unsafe { Atomic::from_raw(ptr) };
+    /// assert_eq!(ten.map(|a| *a.into_inner()), Some(10));
+    /// ```
+    #[inline]
+    pub fn into_raw(self) -> *mut T {
+        let ptr = self.get_raw(Ordering::Relaxed);
+        debug!("into_raw() = {:p}", ptr);
+        // Ownership of the value is passed to the caller
+        forget(self);
+        ptr
+    }
+
     /// Creates new `Atomic` if pointer is not null (like `NonNull`)
     ///
     /// # Safety
@@ -195,6 +280,16 @@ impl<T> Atomic<T> {
     }
 }
 
+/// Aborts if dropped, used to avoid leaving `Atomic` empty when a closure panics
+struct AbortOnUnwind;
+
+impl Drop for AbortOnUnwind {
+    #[inline]
+    fn drop(&mut self) {
+        abort();
+    }
+}
+
 impl<T> From<T> for Atomic<T> {
     #[inline]
     fn from(into_ptr: T) -> Self {
@@ -223,8 +318,9 @@ impl<T> Drop for Atomic<T> {
         info!("Drop");
         // `Box::from_raw` requires unsafe, but since we own what it points to
         // And we do a `null` check we can `Box` it to drop without problems
-        NonNull::new(self.0.swap(null_mut(), Ordering::Relaxed))
-            .map_or((), |nn| drop(unsafe { Box::from_raw(nn.as_ptr()) }));
+        if let Some(nn) = NonNull::new(self.0.swap(null_mut(), Ordering::Relaxed)) {
+            drop(unsafe { Box::from_raw(nn.as_ptr()) });
+        }
     }
 }
 
@@ -232,6 +328,50 @@ impl<T> Drop for Atomic<T> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn into_raw() {
+        let ptr = Box::into_raw(Box::new(vec![1, 2]));
+        let atomic = unsafe { Atomic::from_raw_unchecked(ptr) };
+        assert_eq!(atomic.into_raw(), ptr);
+        assert_eq!(unsafe { Box::from_raw(ptr) }, Box::new(vec![1, 2]));
+    }
+
+    #[test]
+    fn compare_exchange() {
+        use std::sync::Arc;
+        let arc = Arc::new(());
+        let atomic = Atomic::from(Arc::clone(&arc));
+        let current = atomic.get_raw(Ordering::Relaxed);
+        let cas = |current, new| {
+            atomic.compare_exchange(current, Box::new(new), Ordering::AcqRel, Ordering::Acquire)
+        };
+
+        let old = cas(current, Arc::clone(&arc)).unwrap();
+        assert_eq!(&*old as *const Arc<()>, current as *const Arc<()>);
+        drop(old);
+        assert_eq!(Arc::strong_count(&arc), 2);
+
+        // `current` was replaced, so it fails and gives `new` back
+        let (actual, new) = cas(current, Arc::clone(&arc)).unwrap_err();
+        assert_eq!(actual, atomic.get_raw(Ordering::Relaxed));
+        assert_eq!(Arc::strong_count(&arc), 3);
+        drop(new);
+
+        assert!(cas(null_mut(), Arc::new(())).is_err());
+        let old = cas(actual, Arc::new(())).unwrap();
+        drop((old, atomic));
+        assert_eq!(Arc::strong_count(&arc), 1);
+    }
+
+    #[test]
+    fn replace_with() {
+        let mut atomic = Atomic::from(10);
+        atomic.replace_with(|old| Box::new(*old * 2));
+        assert_eq!(*atomic.swap(1, Ordering::Relaxed), 20);
+        atomic.replace_with(|old| old);
+        assert_eq!(*atomic.into_inner(), 1);
+    }
+
     #[test]
     fn test_send() {
         fn assert_send<T: Send>() {}
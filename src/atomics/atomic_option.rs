@@ -11,7 +11,10 @@
 use crate::prelude::*;
 use std::fmt::{self, Debug, Formatter, Pointer};
 use std::sync::atomic::{AtomicPtr, Ordering};
-use std::{marker::PhantomData, mem::drop, ptr::null_mut, ptr::NonNull};
+use std::{marker::PhantomData, mem::drop, mem::forget, ptr::null_mut, ptr::NonNull};
+
+#[cfg(feature = "blocking")]
+use parking_lot_core::{park, unpark_all, DEFAULT_PARK_TOKEN, DEFAULT_UNPARK_TOKEN};
 
 /// Atomic `Option<Box<T>>`
 ///
@@ -32,6 +35,21 @@ impl<T: Debug> Debug for AtomicOption<T> {
 }
 
 impl<T> AtomicOption<T> {
+    /// Creates empty `AtomicOption` in a const context (like `Default::default`, but it can initialize statics)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::AtomicOption;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// static OPTION: AtomicOption<u8> = AtomicOption::empty();
+    /// OPTION.store(Box::new(10), Ordering::Relaxed);
+    /// assert_eq!(OPTION.take(Ordering::Relaxed), Some(Box::new(10)));
+    /// ```
+    #[inline]
+    pub const fn empty() -> Self {
+        AtomicOption(AtomicPtr::new(null_mut()), PhantomData)
+    }
+
     /// Creates new `AtomicOption`
     ///
     /// ```rust
@@ -53,7 +71,7 @@ impl<T> AtomicOption<T> {
 
     /// Stores new value if `AtomicOption` currently contains a `None`
     ///
-    /// This operation is implemented as a single atomic `compare_and_swap`.
+    /// This operation is implemented as a single atomic `compare_exchange`.
     ///
     /// ```rust
     /// # use voluntary_servitude::atomics::AtomicOption;
@@ -73,9 +91,17 @@ impl<T> AtomicOption<T> {
         V: Into<Box<T>>,
     {
         let ptr = new.into().into_ptr();
-        let old = NonNull::new(self.0.compare_and_swap(null_mut(), ptr, order));
+        let old = self
+            .0
+            .compare_exchange(null_mut(), ptr, order, Ordering::Relaxed);
         trace!("try_store({:p}) = {:?})", ptr, old);
-        old.map_or(Ok(()), |_| Err(NotEmpty))
+        if old.is_ok() {
+            Ok(())
+        } else {
+            // If it failed we still own `ptr`, so we must drop it
+            drop(unsafe { Box::from_raw(ptr) });
+            Err(NotEmpty)
+        }
     }
 
     /// Stores value into `AtomicOption` and drops old one
@@ -120,6 +146,34 @@ impl<T> AtomicOption<T> {
         old.map(|nn| unsafe { Box::from_raw(nn.as_ptr()) })
     }
 
+    /// Stores value into `AtomicOption` returning old raw pointer (`null` if it was empty), to compare pointer identity
+    ///
+    /// Ownership of the old value is transferred to the caller, it won't be dropped by `AtomicOption`
+    ///
+    /// If the pointer is not null you must free it (with `Box::from_raw`) or it will leak, and you can't use it after freeing
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::AtomicOption;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// let option = AtomicOption::from(5);
+    /// let ptr = option.swap_raw(None, Ordering::Relaxed);
+    /// assert!(!ptr.is_null());
+    /// // We own the old value now, so we must free it
+    /// assert_eq!(unsafe { Box::from_raw(ptr) }, Box::new(5));
+    /// assert!(option.swap_raw(None, Ordering::Relaxed).is_null());
+    /// ```
+    #[inline]
+    pub fn swap_raw<V>(&self, new: V, order: Ordering) -> *mut T
+    where
+        V: Into<Option<Box<T>>>,
+    {
+        let ptr = new.into().into_ptr();
+        let old = self.0.swap(ptr, order);
+        trace!("swap_raw({:p}) = {:p}", ptr, old);
+        old
+    }
+
     /// Replaces `AtomicOption` value with `None` returning old value
     ///
     /// ```rust
@@ -170,6 +224,26 @@ impl<T> AtomicOption<T> {
         self.swap(None, Ordering::Relaxed)
     }
 
+    /// Converts itself into a raw pointer without dropping the value (the caller takes ownership, `null` if empty)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::AtomicOption;
+    /// # env_logger::init();
+    /// let ptr = AtomicOption::from(10).into_raw();
+    /// let ten = unsafe { AtomicOption::from_raw(ptr) };
+    /// assert_eq!(ten.into_inner().map(|a| *a), Some(10));
+    ///
+    /// assert!(AtomicOption::<()>::default().into_raw().is_null());
+    /// ```
+    #[inline]
+    pub fn into_raw(self) -> *mut T {
+        let ptr = self.get_raw(Ordering::Relaxed);
+        debug!("into_raw() = {:p}", ptr);
+        // Ownership of the value is passed to the caller
+        forget(self);
+        ptr
+    }
+
     /// Creates new `AtomicOption` based on raw pointer
     ///
     /// # Safety
@@ -224,12 +298,240 @@ impl<T> AtomicOption<T> {
         trace!("get_raw({:?})", order);
         self.0.load(order)
     }
+
+    /// Checks if both options point to the same allocation (never derefs, two empty options are equal)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::AtomicOption;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// let option = AtomicOption::from(10);
+    /// assert!(option.ptr_eq(&option, Ordering::Relaxed));
+    /// assert!(!option.ptr_eq(&AtomicOption::from(10), Ordering::Relaxed));
+    /// ```
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self, order: Ordering) -> bool {
+        trace!("ptr_eq({:p}, {:?})", other, order);
+        self.get_raw(order) == other.get_raw(order)
+    }
+}
+
+/// Blocking waits, threads are parked in `parking_lot_core`'s global queue keyed by the `AtomicOption` address (so it costs no space)
+#[cfg(feature = "blocking")]
+impl<T> AtomicOption<T> {
+    /// Key of this `AtomicOption`'s waiters in the parking queue
+    #[inline]
+    fn parking_key(&self) -> usize {
+        &self.0 as *const AtomicPtr<T> as usize
+    }
+
+    /// Wakes every thread waiting for a value
+    #[inline]
+    fn notify(&self) {
+        trace!("notify()");
+        // Only the parking queue is touched, we never park while holding it
+        let _ = unsafe { unpark_all(self.parking_key(), DEFAULT_UNPARK_TOKEN) };
+    }
+
+    /// Parks thread until a notifying store happens (returns immediately if it's filled), wakeups may be spurious
+    #[inline]
+    pub(crate) fn park_while_empty(&self) {
+        trace!("park_while_empty()");
+        // Emptiness is checked with the queue locked, so a store notifying right after it can't be missed
+        // None of the callbacks panic or park, as required by `park`
+        let validate = || self.0.load(Ordering::Relaxed).is_null();
+        let _ = unsafe {
+            park(
+                self.parking_key(),
+                validate,
+                || {},
+                |_, _| {},
+                DEFAULT_PARK_TOKEN,
+                None,
+            )
+        };
+    }
+
+    /// Stores value into `AtomicOption` (dropping old one) and wakes every thread blocked in [`wait_take`]
+    ///
+    /// [`wait_take`]: #method.wait_take
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::AtomicOption;
+    /// # env_logger::init();
+    /// use std::{sync::Arc, sync::atomic::Ordering, thread::spawn};
+    /// let option = Arc::new(AtomicOption::default());
+    /// let waiter = {
+    ///     let option = Arc::clone(&option);
+    ///     spawn(move || option.wait_take(Ordering::Acquire))
+    /// };
+    /// option.store_and_notify(Box::new(5), Ordering::Release);
+    /// assert_eq!(waiter.join().unwrap(), Box::new(5));
+    /// ```
+    #[cfg_attr(docs_rs_workaround, doc(cfg(feature = "blocking")))]
+    #[inline]
+    pub fn store_and_notify<V>(&self, new: V, order: Ordering)
+    where
+        V: Into<Option<Box<T>>>,
+    {
+        self.store(new, order);
+        self.notify();
+    }
+
+    /// Stores new value if `AtomicOption` currently contains a `None`, waking every thread blocked in [`wait_take`] if it did
+    ///
+    /// [`wait_take`]: #method.wait_take
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::AtomicOption;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// let option = AtomicOption::default();
+    /// assert!(option.try_store_and_notify(5, Ordering::Release).is_ok());
+    /// assert!(option.try_store_and_notify(6, Ordering::Release).is_err());
+    /// assert_eq!(option.wait_take(Ordering::Acquire), Box::new(5));
+    /// ```
+    #[cfg_attr(docs_rs_workaround, doc(cfg(feature = "blocking")))]
+    #[inline]
+    pub fn try_store_and_notify<V>(&self, new: V, order: Ordering) -> Result<(), NotEmpty>
+    where
+        V: Into<Box<T>>,
+    {
+        self.try_store(new, order)?;
+        self.notify();
+        Ok(())
+    }
+
+    /// Blocks until there is a value, taking it (leaving `None`)
+    ///
+    /// Only stores done with [`store_and_notify`]/[`try_store_and_notify`] wake it up, other stores are only noticed on the next wakeup
+    ///
+    /// If many threads wait they are all woken, but only one takes the value, the others keep waiting
+    ///
+    /// [`store_and_notify`]: #method.store_and_notify
+    /// [`try_store_and_notify`]: #method.try_store_and_notify
+    #[cfg_attr(docs_rs_workaround, doc(cfg(feature = "blocking")))]
+    #[inline]
+    pub fn wait_take(&self, order: Ordering) -> Box<T> {
+        debug!("wait_take({:?})", order);
+        loop {
+            if let Some(value) = self.take(order) {
+                return value;
+            }
+            self.park_while_empty();
+        }
+    }
+}
+
+impl<T: Clone> AtomicOption<T> {
+    /// Retrieves a clone of the stored value without emptying `AtomicOption`
+    ///
+    /// The value is swapped out, cloned and stored back with a `compare_exchange`
+    ///
+    /// While it's swapped out other threads see `AtomicOption` as empty, if a value is stored in that window the new value is kept and the old one is dropped
+    ///
+    /// If `T::clone` panics the value is still stored back
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::AtomicOption;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// let option = AtomicOption::from(5);
+    /// assert_eq!(option.get_cloned(Ordering::Relaxed), Some(5));
+    /// assert_eq!(option.into_inner().map(|a| *a), Some(5));
+    /// ```
+    #[inline]
+    pub fn get_cloned(&self, order: Ordering) -> Option<T> {
+        trace!("get_cloned({:?})", order);
+        let taken = StoreBack(self, Some(self.take(order)?), order);
+        taken.1.as_deref().cloned()
+    }
+}
+
+/// Stores a taken value back into `AtomicOption` when dropped (even if `clone` panics)
+///
+/// If someone filled it in the meantime their value wins and ours is dropped
+struct StoreBack<'a, T>(&'a AtomicOption<T>, Option<Box<T>>, Ordering);
+
+impl<'a, T> Drop for StoreBack<'a, T> {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(value) = self.1.take() {
+            let _ = self.0.try_store(value, self.2);
+        }
+    }
+}
+
+impl<T: PartialEq> AtomicOption<T> {
+    /// Replaces the stored value with `new` if it's equal to `expected` (compared by value), returning the old value
+    ///
+    /// If it's empty or holds a different value nothing changes and `new` is given back
+    ///
+    /// Values can't be compared atomically, so the value is swapped out, compared and then `new` (or the old value) is stored back with a `compare_exchange`
+    ///
+    /// While it's swapped out other threads see `AtomicOption` as empty, if a value is stored in that window it wins and the value being stored back is dropped, so it's meant for a single writer
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::AtomicOption;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// let option = AtomicOption::from(5);
+    /// assert_eq!(option.compare_and_swap_value(&5, Box::new(6), Ordering::Relaxed), Ok(Box::new(5)));
+    /// assert_eq!(option.compare_and_swap_value(&5, Box::new(7), Ordering::Relaxed), Err(Box::new(7)));
+    /// assert_eq!(option.into_inner().map(|a| *a), Some(6));
+    /// ```
+    #[inline]
+    pub fn compare_and_swap_value(
+        &self,
+        expected: &T,
+        new: Box<T>,
+        order: Ordering,
+    ) -> Result<Box<T>, Box<T>> {
+        trace!("compare_and_swap_value({:?})", order);
+        match self.take(order) {
+            Some(current) if *current == *expected => {
+                // If someone filled it in the meantime their value wins and `new` is dropped
+                let _ = self.try_store(new, order);
+                Ok(current)
+            }
+            Some(current) => {
+                let _ = self.try_store(current, order);
+                Err(new)
+            }
+            None => Err(new),
+        }
+    }
+}
+
+impl<T: Clone> Clone for AtomicOption<T> {
+    /// Creates new independent `AtomicOption` holding a clone of the current value
+    ///
+    /// Uses [`get_cloned`]: the value is taken out while it's cloned, so meanwhile other threads see `None` and if one stores a value in that window it's kept while the cloned one is dropped
+    ///
+    /// If `T::clone` panics the value is stored back
+    ///
+    /// [`get_cloned`]: #method.get_cloned
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::AtomicOption;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// let option = AtomicOption::from(10);
+    /// let cloned = option.clone();
+    /// option.store(None, Ordering::Relaxed);
+    /// assert_eq!(cloned.into_inner().map(|a| *a), Some(10));
+    /// ```
+    #[inline]
+    fn clone(&self) -> Self {
+        trace!("clone()");
+        Self::from(self.get_cloned(Ordering::Relaxed))
+    }
 }
 
 impl<T> Default for AtomicOption<T> {
     #[inline]
     fn default() -> Self {
-        Self::new(None)
+        Self::empty()
     }
 }
 
@@ -298,6 +600,129 @@ impl<T> Drop for AtomicOption<T> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn compare_and_swap_value() {
+        let option = AtomicOption::from(vec![1, 2]);
+        let swapped =
+            option.compare_and_swap_value(&vec![1, 2], Box::new(vec![3]), Ordering::Relaxed);
+        assert_eq!(swapped, Ok(Box::new(vec![1, 2])));
+        let swapped =
+            option.compare_and_swap_value(&vec![1, 2], Box::new(vec![4]), Ordering::Relaxed);
+        assert_eq!(swapped, Err(Box::new(vec![4])));
+        assert_eq!(option.get_cloned(Ordering::Relaxed), Some(vec![3]));
+
+        assert_eq!(option.take(Ordering::Relaxed), Some(Box::new(vec![3])));
+        let swapped = option.compare_and_swap_value(&vec![3], Box::new(vec![5]), Ordering::Relaxed);
+        assert_eq!(swapped, Err(Box::new(vec![5])));
+        assert_eq!(option.take(Ordering::Relaxed), None);
+    }
+
+    #[test]
+    #[cfg(feature = "blocking")]
+    fn wait_take() {
+        use std::{sync::Arc, sync::Barrier, thread::spawn};
+        let option = Arc::new(AtomicOption::default());
+        let barrier = Arc::new(Barrier::new(5));
+        let waiters: Vec<_> = (0..4)
+            .map(|_| {
+                let (option, barrier) = (Arc::clone(&option), Arc::clone(&barrier));
+                spawn(move || {
+                    let _ = barrier.wait();
+                    *option.wait_take(Ordering::Acquire)
+                })
+            })
+            .collect();
+
+        // Each value wakes the waiters and one takes it
+        let _ = barrier.wait();
+        for value in 0..4 {
+            assert!(option
+                .try_store_and_notify(value, Ordering::Release)
+                .is_ok());
+            while !option.get_raw(Ordering::Acquire).is_null() {}
+        }
+
+        let mut taken: Vec<_> = waiters.into_iter().map(|w| w.join().unwrap()).collect();
+        taken.sort_unstable();
+        assert_eq!(taken, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn get_cloned() {
+        let option = AtomicOption::from(vec![1, 2]);
+        assert_eq!(option.get_cloned(Ordering::Relaxed), Some(vec![1, 2]));
+        assert_eq!(option.get_cloned(Ordering::Relaxed), Some(vec![1, 2]));
+        assert_eq!(option.take(Ordering::Relaxed), Some(Box::new(vec![1, 2])));
+        assert_eq!(option.get_cloned(Ordering::Relaxed), None);
+    }
+
+    #[test]
+    fn get_cloned_panic_restores() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        #[derive(Debug, PartialEq)]
+        struct PanicOnClone(u8);
+        impl Clone for PanicOnClone {
+            fn clone(&self) -> Self {
+                panic!("clone")
+            }
+        }
+
+        let option = AtomicOption::from(PanicOnClone(1));
+        let cloned = catch_unwind(AssertUnwindSafe(|| option.get_cloned(Ordering::Relaxed)));
+        assert!(cloned.is_err());
+        assert_eq!(
+            option.take(Ordering::Relaxed),
+            Some(Box::new(PanicOnClone(1)))
+        );
+    }
+
+    #[test]
+    fn clone_is_independent() {
+        let option = AtomicOption::from(1);
+        let cloned = option.clone();
+        assert_eq!(
+            option.swap(Box::new(2), Ordering::Relaxed),
+            Some(Box::new(1))
+        );
+        assert_eq!(cloned.take(Ordering::Relaxed), Some(Box::new(1)));
+        assert_eq!(option.into_inner(), Some(Box::new(2)));
+        assert!(cloned.clone().into_inner().is_none());
+    }
+
+    #[test]
+    fn swap_raw() {
+        let option = AtomicOption::from(1);
+        let first = option.get_raw(Ordering::Relaxed);
+        let old = option.swap_raw(Box::new(2), Ordering::Relaxed);
+        assert_eq!(old, first);
+        assert_ne!(option.get_raw(Ordering::Relaxed), first);
+        assert_eq!(unsafe { Box::from_raw(old) }, Box::new(1));
+        assert_eq!(option.into_inner(), Some(Box::new(2)));
+    }
+
+    #[test]
+    fn into_raw() {
+        let ptr = Box::into_raw(Box::new(vec![1, 2]));
+        let option = unsafe { AtomicOption::from_raw(ptr) };
+        assert_eq!(option.into_raw(), ptr);
+        assert_eq!(unsafe { Box::from_raw(ptr) }, Box::new(vec![1, 2]));
+        assert!(AtomicOption::<u8>::default().into_raw().is_null());
+    }
+
+    #[test]
+    fn ptr_eq() {
+        let ptr = Box::into_raw(Box::new(1));
+        let first = unsafe { AtomicOption::from_raw(ptr) };
+        let second = unsafe { AtomicOption::from_raw(ptr) };
+        assert!(first.ptr_eq(&second, Ordering::Relaxed));
+        assert!(!first.ptr_eq(&AtomicOption::from(1), Ordering::Relaxed));
+        // Both own the same allocation, only one may free it
+        let _ = second.swap_raw(None, Ordering::Relaxed);
+        assert!(!first.ptr_eq(&second, Ordering::Relaxed));
+        assert!(second.ptr_eq(&AtomicOption::default(), Ordering::Relaxed));
+    }
+
     #[test]
     fn test_send() {
         fn assert_send<T: Send>() {}
@@ -33,7 +33,7 @@ impl<T> FillOnceAtomicArc<T> {
 
     /// Stores new `Arc<T>` if `FillOnceAtomicArc` currently contains a `None`
     ///
-    /// This operation is implemented as a single atomic `compare_and_swap`.
+    /// This operation is implemented as a single atomic `compare_exchange`.
     ///
     /// ```rust
     /// # use voluntary_servitude::atomics::FillOnceAtomicArc;
@@ -56,6 +56,26 @@ impl<T> FillOnceAtomicArc<T> {
         self.0.try_store(data.into(), order)
     }
 
+    /// Replaces `FillOnceAtomicArc` value with `None` returning old value
+    ///
+    /// As opposed to `take` from [`AtomicOption`]
+    ///
+    /// [`AtomicOption`]: ./struct.AtomicOption.html#method.take
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::FillOnceAtomicArc;
+    /// # env_logger::init();
+    /// use std::sync::{Arc, atomic::Ordering};
+    /// let mut option = FillOnceAtomicArc::from(5);
+    /// assert_eq!(option.take(Ordering::Relaxed), Some(Arc::new(5)));
+    /// assert_eq!(option.take(Ordering::Relaxed), None);
+    /// ```
+    #[inline]
+    pub fn take(&mut self, order: Ordering) -> Option<Arc<T>> {
+        info!("take()");
+        self.0.take(order).map(|arc| *arc)
+    }
+
     /// Atomically retrieves a cloned `Option<Arc<T>>`
     ///
     /// ```rust
@@ -90,6 +110,22 @@ impl<T> FillOnceAtomicArc<T> {
         self.0.get_ref(order).map(|arc| &**arc)
     }
 
+    /// Creates new `FillOnceAtomicArc` holding `f` applied to the current value (empty if it's empty)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::FillOnceAtomicArc;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// let port = FillOnceAtomicArc::from(8080);
+    /// let address = port.map(Ordering::Relaxed, |port| format!("localhost:{}", port));
+    /// assert_eq!(address.get_ref(Ordering::Relaxed).map(String::as_str), Some("localhost:8080"));
+    /// ```
+    #[inline]
+    pub fn map<U, F: FnOnce(&T) -> U>(&self, order: Ordering, f: F) -> FillOnceAtomicArc<U> {
+        trace!("map({:?})", order);
+        FillOnceAtomicArc::from(self.get_ref(order).map(f).map(Arc::new))
+    }
+
     /// Converts itself into a `Option<Arc<T>>`
     ///
     /// ```rust
@@ -203,6 +239,30 @@ impl<T: Debug> Debug for FillOnceAtomicArc<T> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn take() {
+        let mut atomic = FillOnceAtomicArc::from(10);
+        assert_eq!(atomic.take(Ordering::Relaxed), Some(Arc::new(10)));
+        assert_eq!(atomic.get_ref(Ordering::Relaxed), None);
+        assert!(atomic.try_store(5, Ordering::Relaxed).is_ok());
+        assert_eq!(atomic.get_ref(Ordering::Relaxed), Some(&5));
+    }
+
+    #[test]
+    fn map() {
+        let filled = FillOnceAtomicArc::from(-10);
+        let mapped = filled.map(Ordering::Relaxed, i32::to_string);
+        assert_eq!(mapped.get_ref(Ordering::Relaxed), Some(&"-10".to_owned()));
+        assert_eq!(filled.get_ref(Ordering::Relaxed), Some(&-10));
+
+        let empty: FillOnceAtomicArc<i32> = FillOnceAtomicArc::default();
+        let mapped = empty.map(Ordering::Relaxed, i32::to_string);
+        assert_eq!(mapped.get_ref(Ordering::Relaxed), None);
+        assert!(mapped
+            .try_store("filled".to_owned(), Ordering::Relaxed)
+            .is_ok());
+    }
+
     #[test]
     fn test_send() {
         fn assert_send<T: Send>() {}
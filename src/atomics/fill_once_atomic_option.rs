@@ -6,7 +6,7 @@
 
 use crate::prelude::*;
 use std::fmt::{self, Debug, Formatter, Pointer};
-use std::{ptr::NonNull, sync::atomic::Ordering};
+use std::{any::type_name, ptr::null_mut, ptr::NonNull, sync::atomic::Ordering};
 
 /// Atomic abstraction of a `Option<Box<T>>` that can provide access to a `Option<&T>`
 ///
@@ -16,6 +16,22 @@ use std::{ptr::NonNull, sync::atomic::Ordering};
 pub struct FillOnceAtomicOption<T>(AtomicOption<T>);
 
 impl<T> FillOnceAtomicOption<T> {
+    /// Creates empty `FillOnceAtomicOption` in a const context (like `Default::default`, but it can initialize statics)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::FillOnceAtomicOption;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// static CONFIG: FillOnceAtomicOption<String> = FillOnceAtomicOption::empty();
+    /// assert_eq!(CONFIG.get_ref(Ordering::Acquire), None);
+    /// CONFIG.set_once("debug".to_owned(), Ordering::Release);
+    /// assert_eq!(CONFIG.get_ref(Ordering::Acquire).map(String::as_str), Some("debug"));
+    /// ```
+    #[inline]
+    pub const fn empty() -> Self {
+        FillOnceAtomicOption(AtomicOption::empty())
+    }
+
     /// Creates new `FillOnceAtomicOption`
     ///
     /// ```rust
@@ -38,7 +54,7 @@ impl<T> FillOnceAtomicOption<T> {
 
     /// Stores new value if `FillOnceAtomicOption` was not initialized (contains a `None`)
     ///
-    /// This operation is implemented as a single atomic `compare_and_swap`.
+    /// This operation is implemented as a single atomic `compare_exchange`.
     ///
     /// ```rust
     /// # use voluntary_servitude::atomics::FillOnceAtomicOption;
@@ -61,6 +77,63 @@ impl<T> FillOnceAtomicOption<T> {
         self.0.try_store(data, order)
     }
 
+    /// Stores new value if `FillOnceAtomicOption` was not initialized, otherwise gives the value back
+    ///
+    /// Like [`try_store`], but the value isn't dropped on failure
+    ///
+    /// [`try_store`]: #method.try_store
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::FillOnceAtomicOption;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// let option = FillOnceAtomicOption::from(5);
+    /// assert_eq!(option.try_store_back(10, Ordering::Relaxed), Err(Box::new(10)));
+    /// assert_eq!(option.get_ref(Ordering::Relaxed), Some(&5));
+    /// ```
+    #[inline]
+    pub fn try_store_back<V>(&self, data: V, order: Ordering) -> Result<(), Box<T>>
+    where
+        V: Into<Box<T>>,
+    {
+        let ptr = data.into().into_ptr();
+        // It only replaces `null`, so no stored value is ever replaced (or dropped) while referenced
+        let atomic_ptr = unsafe { self.0.atomic_ptr() };
+        let old = atomic_ptr.compare_exchange(null_mut(), ptr, order, Ordering::Relaxed);
+        trace!("try_store_back({:p}) = {:?}", ptr, old);
+        // If it failed we still own `ptr`, so we give it back
+        old.map(|_| ()).map_err(|_| unsafe { Box::from_raw(ptr) })
+    }
+
+    /// Stores new value, for when `FillOnceAtomicOption` must only be filled once and a double fill is a bug
+    ///
+    /// # Panics
+    ///
+    /// Panics if `FillOnceAtomicOption` was already filled (where [`try_store`] would return `Err(NotEmpty)`)
+    ///
+    /// [`try_store`]: #method.try_store
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::FillOnceAtomicOption;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// let option = FillOnceAtomicOption::default();
+    /// option.set_once(5, Ordering::Relaxed);
+    /// assert_eq!(option.get_ref(Ordering::Relaxed), Some(&5));
+    /// ```
+    #[inline]
+    pub fn set_once<V>(&self, data: V, order: Ordering)
+    where
+        V: Into<Box<T>>,
+    {
+        if self.try_store(data, order).is_err() {
+            panic!(
+                "FillOnceAtomicOption<{}> was already filled",
+                type_name::<T>()
+            );
+        }
+    }
+
     /// Replaces `FillOnceAtomicOption` value with `None` returning old value
     ///
     /// As opposed to `take` from [`AtomicOption`]
@@ -116,13 +189,57 @@ impl<T> FillOnceAtomicOption<T> {
     /// assert_eq!(filled.get_ref(Ordering::Relaxed), Some(&10));
     /// ```
     #[inline]
-    pub fn get_ref<'a>(&'a self, order: Ordering) -> Option<&'a T> {
+    pub fn get_ref(&self, order: Ordering) -> Option<&T> {
         let raw = self.0.get_raw(order);
         debug!("FillOnceAtomicOption get_ref: {:p}", raw);
         // This specific API ensures that the pointer is either `null`or won't ever change, so we can get a ref to it (with the same lifetime as `Self`)
         NonNull::new(raw).map(|nn| unsafe { &*nn.as_ptr() })
     }
 
+    /// Stores new value if `FillOnceAtomicOption` was not initialized, waking every thread blocked in [`wait_filled`] if it did
+    ///
+    /// [`wait_filled`]: #method.wait_filled
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::FillOnceAtomicOption;
+    /// # env_logger::init();
+    /// use std::{sync::Arc, sync::atomic::Ordering, thread::spawn};
+    /// let option = Arc::new(FillOnceAtomicOption::default());
+    /// let waiter = {
+    ///     let option = Arc::clone(&option);
+    ///     spawn(move || *option.wait_filled(Ordering::Acquire))
+    /// };
+    /// assert!(option.try_store_and_notify(5, Ordering::Release).is_ok());
+    /// assert_eq!(waiter.join().unwrap(), 5);
+    /// ```
+    #[cfg(feature = "blocking")]
+    #[cfg_attr(docs_rs_workaround, doc(cfg(feature = "blocking")))]
+    #[inline]
+    pub fn try_store_and_notify<V>(&self, data: V, order: Ordering) -> Result<(), NotEmpty>
+    where
+        V: Into<Box<T>>,
+    {
+        self.0.try_store_and_notify(data, order)
+    }
+
+    /// Blocks until it's filled, returning a reference to the value
+    ///
+    /// Only [`try_store_and_notify`] wakes it up, other stores are only noticed on the next wakeup
+    ///
+    /// [`try_store_and_notify`]: #method.try_store_and_notify
+    #[cfg(feature = "blocking")]
+    #[cfg_attr(docs_rs_workaround, doc(cfg(feature = "blocking")))]
+    #[inline]
+    pub fn wait_filled(&self, order: Ordering) -> &T {
+        debug!("wait_filled({:?})", order);
+        loop {
+            if let Some(value) = self.get_ref(order) {
+                return value;
+            }
+            self.0.park_while_empty();
+        }
+    }
+
     /// Converts itself into a `Option<Box<T>>`
     ///
     /// ```rust
@@ -183,12 +300,37 @@ impl<T> FillOnceAtomicOption<T> {
     pub fn get_raw(&self, order: Ordering) -> *mut T {
         self.0.get_raw(order)
     }
+
+    /// Stores `ptr` if it's empty, without rebuilding a `Box` (so copies of `ptr` keep the same provenance)
+    ///
+    /// # Safety
+    ///
+    /// You must own `ptr` (it must come from `Box::into_raw`), if it fails you still own it
+    #[inline]
+    pub(crate) unsafe fn try_store_raw(
+        &self,
+        ptr: NonNull<T>,
+        order: Ordering,
+    ) -> Result<(), NotEmpty> {
+        trace!("try_store_raw({:p}, {:?})", ptr, order);
+        let atomic_ptr = self.0.atomic_ptr();
+        let old = atomic_ptr.compare_exchange(null_mut(), ptr.as_ptr(), order, Ordering::Relaxed);
+        old.map(|_| ()).map_err(|_| NotEmpty)
+    }
+
+    /// Extracts the stored pointer leaving it empty, without rebuilding a `Box` (the caller takes ownership)
+    #[inline]
+    pub(crate) fn take_raw(&mut self, order: Ordering) -> Option<NonNull<T>> {
+        trace!("take_raw({:?})", order);
+        // Exclusive access means nobody holds a reference to the value
+        NonNull::new(unsafe { self.0.atomic_ptr() }.swap(null_mut(), order))
+    }
 }
 
 impl<T> Default for FillOnceAtomicOption<T> {
     #[inline]
     fn default() -> Self {
-        Self::new(None)
+        Self::empty()
     }
 }
 
@@ -257,6 +399,66 @@ impl<T: Debug> Debug for FillOnceAtomicOption<T> {
 mod tests {
     use super::*;
 
+    #[test]
+    #[cfg(feature = "blocking")]
+    fn wait_filled() {
+        use std::{sync::Arc, sync::Barrier, thread::spawn};
+        let option = Arc::new(FillOnceAtomicOption::default());
+        let barrier = Arc::new(Barrier::new(5));
+        let waiters: Vec<_> = (0..4)
+            .map(|_| {
+                let (option, barrier) = (Arc::clone(&option), Arc::clone(&barrier));
+                spawn(move || {
+                    let _ = barrier.wait();
+                    *option.wait_filled(Ordering::Acquire)
+                })
+            })
+            .collect();
+
+        let _ = barrier.wait();
+        assert!(option.try_store_and_notify(10, Ordering::Release).is_ok());
+        for waiter in waiters {
+            assert_eq!(waiter.join().unwrap(), 10);
+        }
+        assert_eq!(option.wait_filled(Ordering::Acquire), &10);
+    }
+
+    #[test]
+    fn static_fill_once() {
+        static ONCE: FillOnceAtomicOption<Vec<u8>> = FillOnceAtomicOption::empty();
+        assert_eq!(ONCE.get_ref(Ordering::Acquire), None);
+        let threads: Vec<_> = (0..4u8)
+            .map(|i| std::thread::spawn(move || ONCE.try_store(vec![i], Ordering::Release)))
+            .collect();
+        let stored = threads.into_iter().map(|t| t.join().unwrap());
+        assert_eq!(stored.filter(Result::is_ok).count(), 1);
+        assert_eq!(ONCE.get_ref(Ordering::Acquire).map(Vec::len), Some(1));
+    }
+
+    #[test]
+    fn try_store_back() {
+        let atomic = FillOnceAtomicOption::default();
+        assert_eq!(atomic.try_store_back(vec![1], Ordering::Relaxed), Ok(()));
+        let back = atomic.try_store_back(vec![2, 3], Ordering::Relaxed);
+        assert_eq!(back, Err(Box::new(vec![2, 3])));
+        assert_eq!(atomic.get_ref(Ordering::Relaxed), Some(&vec![1]));
+    }
+
+    #[test]
+    fn set_once() {
+        let atomic = FillOnceAtomicOption::default();
+        atomic.set_once(10, Ordering::Relaxed);
+        assert_eq!(atomic.get_ref(Ordering::Relaxed), Some(&10));
+    }
+
+    #[test]
+    #[should_panic(expected = "FillOnceAtomicOption<i32> was already filled")]
+    fn set_once_twice() {
+        let atomic = FillOnceAtomicOption::default();
+        atomic.set_once(10i32, Ordering::Relaxed);
+        atomic.set_once(20, Ordering::Relaxed);
+    }
+
     #[test]
     fn fill_once_ref() {
         let atomic = FillOnceAtomicOption::from(Some(10));
@@ -4,8 +4,10 @@ mod atomic;
 mod atomic_option;
 mod fill_once_atomic_arc;
 mod fill_once_atomic_option;
+mod thin_atomic_option;
 
 pub use self::atomic::Atomic;
 pub use self::atomic_option::AtomicOption;
 pub use self::fill_once_atomic_arc::FillOnceAtomicArc;
 pub use self::fill_once_atomic_option::FillOnceAtomicOption;
+pub use self::thin_atomic_option::ThinAtomicOption;
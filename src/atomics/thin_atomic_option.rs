@@ -0,0 +1,238 @@
+//! Atomic `Option<Box<T>>` for unsized types (like trait objects and slices)
+//!
+//! `AtomicPtr` can only store thin pointers, so the fat `Box<T>` is boxed again and the thin pointer to it is stored
+//!
+//! This costs an extra allocation per stored value and an extra indirection to reach it
+//!
+//! It can't provide a reference to the current value since it may be dropped at any time, you must swap the element to access it
+
+use crate::prelude::*;
+use std::fmt::{self, Debug, Formatter, Pointer};
+use std::sync::atomic::Ordering;
+
+/// Atomic `Option<Box<T>>` that supports `?Sized` types (like `dyn Fn()` or `[u8]`)
+///
+/// The fat `Box<T>` is stored in a thin allocation, so each stored value costs an extra allocation and indirection
+///
+/// It can't provide a reference to the current value since it may be dropped at any time, you must swap the element to access it
+///
+/// ```rust
+/// # use voluntary_servitude::atomics::ThinAtomicOption;
+/// # env_logger::init();
+/// use std::sync::atomic::Ordering;
+/// let handler: ThinAtomicOption<dyn Fn(u8) -> u8> = ThinAtomicOption::new(None);
+/// handler.store(Some(Box::new(|x| x + 1)), Ordering::Relaxed);
+/// let old = handler.swap(Some(Box::new(|x| x * 2)), Ordering::Relaxed);
+/// assert_eq!(old.map(|f| f(3)), Some(4));
+/// assert_eq!(handler.take(Ordering::Relaxed).map(|f| f(3)), Some(6));
+/// ```
+///
+/// It can only be shared between threads if `T: Send`, since any of them can take the value
+///
+/// ```compile_fail
+/// # use voluntary_servitude::atomics::ThinAtomicOption;
+/// fn assert_sync<T: Sync>() {}
+/// assert_sync::<ThinAtomicOption<dyn Fn() + Sync>>();
+/// ```
+pub struct ThinAtomicOption<T: ?Sized>(AtomicOption<Box<T>>);
+
+// Values are only moved in and out (never referenced), so sharing it moves them between threads (like `Mutex<T>`)
+unsafe impl<T: ?Sized + Send> Sync for ThinAtomicOption<T> {}
+
+impl<T: ?Sized> Debug for ThinAtomicOption<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("ThinAtomicOption")
+            .field(&self.0.get_raw(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl<T: ?Sized> ThinAtomicOption<T> {
+    /// Creates empty `ThinAtomicOption` in a const context (like `Default::default`, but it can initialize statics)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::ThinAtomicOption;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// static HANDLER: ThinAtomicOption<dyn Fn() -> u8 + Send + Sync> = ThinAtomicOption::empty();
+    /// HANDLER.store(Some(Box::new(|| 3)), Ordering::Release);
+    /// assert_eq!(HANDLER.take(Ordering::Acquire).map(|f| f()), Some(3));
+    /// ```
+    #[inline]
+    pub const fn empty() -> Self {
+        ThinAtomicOption(AtomicOption::empty())
+    }
+
+    /// Creates new `ThinAtomicOption`
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::ThinAtomicOption;
+    /// # env_logger::init();
+    /// let empty: ThinAtomicOption<[u8]> = ThinAtomicOption::new(None);
+    /// assert!(empty.into_inner().is_none());
+    ///
+    /// let filled: ThinAtomicOption<[u8]> = ThinAtomicOption::new(Some(vec![1, 2].into()));
+    /// assert_eq!(filled.into_inner().as_deref(), Some(&[1, 2][..]));
+    /// ```
+    #[inline]
+    pub fn new(value: Option<Box<T>>) -> Self {
+        ThinAtomicOption(AtomicOption::from(value.map(Box::new)))
+    }
+
+    /// Stores new value if `ThinAtomicOption` currently contains a `None`
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::ThinAtomicOption;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// let option: ThinAtomicOption<str> = ThinAtomicOption::default();
+    /// assert!(option.try_store("a".into(), Ordering::Relaxed).is_ok());
+    /// assert!(option.try_store("b".into(), Ordering::Relaxed).is_err());
+    /// assert_eq!(option.into_inner().as_deref(), Some("a"));
+    /// ```
+    #[inline]
+    pub fn try_store(&self, new: Box<T>, order: Ordering) -> Result<(), NotEmpty> {
+        self.0.try_store(Box::new(new), order)
+    }
+
+    /// Stores value into `ThinAtomicOption` and drops old one
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::ThinAtomicOption;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// let option: ThinAtomicOption<str> = ThinAtomicOption::default();
+    /// option.store(Some("a".into()), Ordering::Relaxed);
+    /// assert_eq!(option.into_inner().as_deref(), Some("a"));
+    /// ```
+    #[inline]
+    pub fn store(&self, new: Option<Box<T>>, order: Ordering) {
+        self.0.store(new.map(Box::new), order)
+    }
+
+    /// Stores value into `ThinAtomicOption` returning old value
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::ThinAtomicOption;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// let option: ThinAtomicOption<[u8]> = ThinAtomicOption::default();
+    /// assert_eq!(option.swap(Some(vec![1].into()), Ordering::Relaxed), None);
+    /// assert_eq!(option.swap(None, Ordering::Relaxed).as_deref(), Some(&[1][..]));
+    /// ```
+    #[inline]
+    pub fn swap(&self, new: Option<Box<T>>, order: Ordering) -> Option<Box<T>> {
+        trace!("swap()");
+        self.0.swap(new.map(Box::new), order).map(|boxed| *boxed)
+    }
+
+    /// Replaces `ThinAtomicOption` value with `None` returning old value
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::ThinAtomicOption;
+    /// # env_logger::init();
+    /// use std::sync::atomic::Ordering;
+    /// let option: ThinAtomicOption<str> = ThinAtomicOption::new(Some("a".into()));
+    /// assert_eq!(option.take(Ordering::Relaxed).as_deref(), Some("a"));
+    /// assert_eq!(option.take(Ordering::Relaxed), None);
+    /// ```
+    #[inline]
+    pub fn take(&self, order: Ordering) -> Option<Box<T>> {
+        self.swap(None, order)
+    }
+
+    /// Converts itself into a `Option<Box<T>>`
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::atomics::ThinAtomicOption;
+    /// # env_logger::init();
+    /// let option: ThinAtomicOption<str> = ThinAtomicOption::from(Box::from("a"));
+    /// assert_eq!(option.into_inner().as_deref(), Some("a"));
+    /// ```
+    #[inline]
+    pub fn into_inner(self) -> Option<Box<T>> {
+        self.0.into_inner().map(|boxed| *boxed)
+    }
+}
+
+impl<T: ?Sized> Default for ThinAtomicOption<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T: ?Sized> From<Box<T>> for ThinAtomicOption<T> {
+    #[inline]
+    fn from(boxed: Box<T>) -> Self {
+        Self::new(Some(boxed))
+    }
+}
+
+impl<T: ?Sized> From<Option<Box<T>>> for ThinAtomicOption<T> {
+    #[inline]
+    fn from(boxed: Option<Box<T>>) -> Self {
+        Self::new(boxed)
+    }
+}
+
+impl<T: ?Sized> Pointer for ThinAtomicOption<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Pointer::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+
+    #[test]
+    fn swap_closures() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let handler: ThinAtomicOption<dyn Fn() -> usize> = ThinAtomicOption::default();
+        assert!(handler.swap(None, Ordering::Relaxed).is_none());
+
+        for i in 1..4 {
+            let calls = Arc::clone(&calls);
+            let new: Box<dyn Fn() -> usize> =
+                Box::new(move || calls.fetch_add(i, Ordering::Relaxed));
+            if let Some(old) = handler.swap(Some(new), Ordering::Relaxed) {
+                let _ = old();
+            }
+        }
+        assert_eq!(calls.load(Ordering::Relaxed), 1 + 2);
+        assert_eq!(handler.take(Ordering::Relaxed).map(|f| f()), Some(3));
+        assert_eq!(calls.load(Ordering::Relaxed), 6);
+
+        let new: Box<dyn Fn() -> usize> = Box::new(|| 0);
+        handler.store(Some(new), Ordering::Relaxed);
+        drop(handler);
+        // Every closure (and its clone of `calls`) was dropped
+        assert_eq!(Arc::strong_count(&calls), 1);
+    }
+
+    #[test]
+    fn slices() {
+        let option: ThinAtomicOption<[u8]> = ThinAtomicOption::from(Box::from(&[1, 2][..]));
+        assert!(option.try_store(Box::new([3]), Ordering::Relaxed).is_err());
+        assert_eq!(option.take(Ordering::Relaxed).as_deref(), Some(&[1, 2][..]));
+        assert!(option.try_store(Box::new([3]), Ordering::Relaxed).is_ok());
+        assert_eq!(option.into_inner().as_deref(), Some(&[3][..]));
+    }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<ThinAtomicOption<dyn Fn() + Send>>();
+    }
+
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<ThinAtomicOption<dyn Fn() + Send + Sync>>();
+    }
+}
@@ -0,0 +1,183 @@
+//! Buffer of values to be appended to [`VoluntaryServitude`] (also called [`VS`]) at once
+//!
+//! [`VoluntaryServitude`]: ./struct.VoluntaryServitude.html
+//! [`VS`]: ./type.VS.html
+
+use crate::{prelude::*, voluntary_servitude::Inner};
+use std::fmt::{self, Debug, Formatter};
+use std::mem::take;
+
+/// Buffers values locally and appends all of them to [`VS`] as a single chain
+///
+/// Avoids contending on the list's last node and size for every value (only once per commit)
+///
+/// Values are appended when `commit` is called or when the `Batch` is dropped
+///
+/// ```rust
+/// # use voluntary_servitude::vs;
+/// # env_logger::init();
+/// let list = vs![];
+/// {
+///     let mut batch = list.batch();
+///     for i in 0..3 {
+///         batch.push(i);
+///     }
+///     // Nothing is appended until the batch is dropped (or committed)
+///     assert!(list.is_empty());
+/// }
+/// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&0, &1, &2]);
+/// ```
+///
+/// [`VS`]: ./type.VS.html
+pub struct Batch<'a, T> {
+    /// List that will receive the buffered values
+    vs: &'a VoluntaryServitude<T>,
+    /// Buffered values
+    inner: Inner<T>,
+}
+
+impl<'a, T> From<&'a VoluntaryServitude<T>> for Batch<'a, T> {
+    #[inline]
+    fn from(vs: &'a VoluntaryServitude<T>) -> Self {
+        trace!("From<&VoluntaryServitude<T>>");
+        Self {
+            vs,
+            inner: Inner::default(),
+        }
+    }
+}
+
+impl<T> Batch<'_, T> {
+    /// Buffers value to be appended on commit
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![];
+    /// let mut batch = list.batch();
+    /// batch.push(1);
+    /// assert_eq!(batch.len(), 1);
+    /// ```
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        self.inner.push(value);
+    }
+
+    /// Returns number of buffered values
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2];
+    /// let mut batch = list.batch();
+    /// assert_eq!(batch.len(), 0);
+    /// batch.push(3);
+    /// assert_eq!(batch.len(), 1);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Checks if there are no buffered values
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2];
+    /// let mut batch = list.batch();
+    /// assert!(batch.is_empty());
+    /// batch.push(3);
+    /// assert!(!batch.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Appends buffered values to `VS` (same as dropping the `Batch`)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1];
+    /// let mut batch = list.batch();
+    /// batch.push(2);
+    /// batch.commit();
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2]);
+    /// ```
+    #[inline]
+    pub fn commit(self) {
+        trace!("commit()");
+    }
+}
+
+impl<T> Extend<T> for Batch<'_, T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T> Drop for Batch<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        debug!("Drop Batch of {}", self.inner.len());
+        self.vs.append_inner(take(&mut self.inner));
+    }
+}
+
+impl<T: Debug> Debug for Batch<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Batch")
+            .field("vs", &self.vs)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{setup_logger, VS};
+
+    #[test]
+    fn batch_matches_append() {
+        setup_logger();
+        let appended: VS<usize> = vs![];
+        let batched: VS<usize> = vs![];
+        for i in 0..100 {
+            appended.append(i);
+        }
+
+        let mut batch = batched.batch();
+        for i in 0..100 {
+            batch.push(i);
+        }
+        assert!(batched.is_empty());
+        batch.commit();
+
+        assert_eq!(batched.len(), appended.len());
+        assert_eq!(batched, appended);
+        batched.append(100);
+        assert_eq!(batched.last(), Some(100));
+    }
+
+    #[test]
+    fn batch_drop_and_empty() {
+        setup_logger();
+        let vs = vs![1];
+        let generation = vs.generation();
+        drop(vs.batch());
+        assert_eq!(vs.generation(), generation);
+
+        {
+            let mut batch = vs.batch();
+            batch.extend(vec![2, 3]);
+            vs.append(4);
+        }
+        assert_eq!(vs.iter().collect::<Vec<_>>(), vec![&1, &4, &2, &3]);
+    }
+}
@@ -0,0 +1,209 @@
+//! Immutable, indexable snapshot of [`VoluntaryServitude`] (also called [`VS`])
+//!
+//! [`VoluntaryServitude`]: ./struct.VoluntaryServitude.html
+//! [`VS`]: ./type.VS.html
+
+#[cfg(any(feature = "logs", feature = "tracing"))]
+use crate::prelude::*;
+use crate::{node::Node, voluntary_servitude::Inner};
+use std::fmt::{self, Debug, Formatter};
+use std::{ops::Index, ptr::NonNull, sync::Arc};
+
+/// Immutable snapshot of [`VS`] that doesn't grow, so it can be indexed and iterated in both directions
+///
+/// Keeps the snapshotted chain alive (like [`Iter`]), later appends to `VS` aren't visible
+///
+/// ```rust
+/// # use voluntary_servitude::vs;
+/// # env_logger::init();
+/// let vs = vs![1, 2, 3];
+/// let frozen = vs.freeze();
+/// vs.append(4);
+///
+/// assert_eq!(frozen.len(), 3);
+/// assert_eq!(frozen[1], 2);
+/// assert_eq!(frozen.get(3), None);
+/// assert_eq!(frozen.iter().rev().collect::<Vec<_>>(), vec![&3, &2, &1]);
+/// ```
+///
+/// [`VS`]: ./type.VS.html
+/// [`Iter`]: ./struct.Iter.html
+pub struct Frozen<T> {
+    /// Keeps nodes alive
+    inner: Arc<Inner<T>>,
+    /// Values in snapshot, in order
+    values: Vec<NonNull<T>>,
+}
+
+// `Frozen` only gives shared references to values owned by `inner`, just like `Arc<Inner<T>>`
+unsafe impl<T: Send + Sync> Send for Frozen<T> {}
+unsafe impl<T: Send + Sync> Sync for Frozen<T> {}
+
+impl<T> Clone for Frozen<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            values: self.values.clone(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for Frozen<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Frozen")
+            .field(&self.iter().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<T> From<Arc<Inner<T>>> for Frozen<T> {
+    #[inline]
+    fn from(inner: Arc<Inner<T>>) -> Self {
+        trace!("From<Arc<Inner<T>>>");
+        let len = inner.len();
+        let mut values = Vec::with_capacity(len);
+        let mut node = inner.first_node();
+        while let Some(nn) = node.filter(|_| values.len() < len) {
+            // We can deref its pointer because `inner` owns it and we own `inner`
+            let node_ref: &Node<T> = unsafe { nn.as_ref() };
+            let missing = len - values.len();
+            values.extend(node_ref.values().iter().take(missing).map(NonNull::from));
+            node = node_ref.next().map(NonNull::from);
+        }
+        Self { inner, values }
+    }
+}
+
+impl<T> Frozen<T> {
+    /// Returns snapshot's length (it never changes)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let vs = vs![1, 2];
+    /// let frozen = vs.freeze();
+    /// vs.append(3);
+    /// assert_eq!(frozen.len(), 2);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Checks if snapshot is empty
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::{vs, VS};
+    /// # env_logger::init();
+    /// let vs: VS<()> = vs![];
+    /// let frozen = vs.freeze();
+    /// vs.append(());
+    /// assert!(frozen.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns reference to element at `index` (`None` if out of bounds)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let frozen = vs![1, 2].freeze();
+    /// assert_eq!(frozen.get(1), Some(&2));
+    /// assert_eq!(frozen.get(2), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        trace!("get({})", index);
+        // We can deref its pointer because `inner` owns it and we own `inner`
+        self.values.get(index).map(|nn| unsafe { nn.as_ref() })
+    }
+
+    /// Makes double-ended iterator over snapshot's elements
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let frozen = vs![1, 2, 3].freeze();
+    /// assert_eq!(frozen.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    /// assert_eq!(frozen.iter().rev().collect::<Vec<_>>(), vec![&3, &2, &1]);
+    /// assert_eq!(frozen.iter().len(), 3);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        trace!("iter()");
+        // We can deref its pointer because `inner` owns it and we own `inner`
+        self.values.iter().map(|nn| unsafe { nn.as_ref() })
+    }
+}
+
+impl<T> Index<usize> for Frozen<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{setup_logger, VS};
+
+    #[test]
+    fn frozen_index() {
+        setup_logger();
+        let vs = vs![1, 2, 3];
+        let frozen = vs.freeze();
+        assert_eq!((frozen[0], frozen[1], frozen[2]), (1, 2, 3));
+        assert_eq!(frozen.get(3), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn frozen_index_out_of_bounds() {
+        let _ = vs![1].freeze()[1];
+    }
+
+    #[test]
+    fn frozen_rev() {
+        setup_logger();
+        let frozen = vs![1, 2, 3, 4].freeze();
+        assert_eq!(
+            frozen.iter().rev().collect::<Vec<_>>(),
+            vec![&4, &3, &2, &1]
+        );
+        let mut iter = frozen.iter();
+        assert_eq!((iter.next(), iter.next_back()), (Some(&1), Some(&4)));
+        assert_eq!(iter.len(), 2);
+    }
+
+    #[test]
+    fn frozen_independent() {
+        setup_logger();
+        let vs = vs![1, 2];
+        let frozen = vs.freeze();
+        vs.append(3);
+        vs.clear();
+        vs.append(4);
+        assert_eq!(frozen.len(), 2);
+        assert_eq!(frozen.iter().collect::<Vec<_>>(), vec![&1, &2]);
+        assert!(VS::<()>::new().freeze().is_empty());
+    }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<super::Frozen<()>>();
+    }
+
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<super::Frozen<()>>();
+    }
+}
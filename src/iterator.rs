@@ -3,11 +3,12 @@
 //! [`VoluntaryServitude`]: ./struct.VoluntaryServitude.html
 //! [`VS`]: ./type.VS.html
 
-#[cfg(feature = "logs")]
+#[cfg(any(feature = "logs", feature = "tracing"))]
 use crate::prelude::*;
-use crate::{node::Node, voluntary_servitude::Inner};
+use crate::{node::Node, voluntary_servitude::Inner, VoluntaryServitude};
 use std::fmt::{self, Debug, Formatter};
-use std::{iter::FusedIterator, ptr::NonNull, sync::Arc};
+use std::sync::{Arc, Weak};
+use std::{iter::from_fn, iter::FusedIterator, ops::ControlFlow, ptr::NonNull};
 
 /// Lock-free iterator based on [`VS`]
 ///
@@ -37,6 +38,8 @@ pub struct Iter<T> {
     inner: Arc<Inner<T>>,
     /// Current node in iteration
     current: Option<NonNull<Node<T>>>,
+    /// Offset of the next value inside current node
+    offset: usize,
     /// Iteration index
     index: usize,
 }
@@ -47,6 +50,7 @@ impl<T> Clone for Iter<T> {
         Self {
             inner: Arc::clone(&self.inner),
             current: self.current,
+            offset: self.offset,
             index: self.index,
         }
     }
@@ -60,6 +64,7 @@ impl<T: Debug> Debug for Iter<T> {
         f.debug_struct("Iter")
             .field("inner", &self.inner)
             .field("current", &curr)
+            .field("offset", &self.offset)
             .field("index", &self.index)
             .finish()
     }
@@ -72,15 +77,66 @@ impl<T> From<Arc<Inner<T>>> for Iter<T> {
         Self {
             current: inner.first_node(),
             inner,
+            offset: 0,
             index: 0,
         }
     }
 }
 
 impl<T> Iter<T> {
+    /// Creates iterator over `inner` already advanced to `index` (consumed if `index` is past the end)
+    #[inline]
+    pub(crate) fn iter_from(inner: Arc<Inner<T>>, index: usize) -> Self {
+        trace!("iter_from({})", index);
+        let mut iter = Self::from(inner);
+        while iter.index < index && (&mut iter).next().is_some() {}
+        iter
+    }
+
+    /// Walks the chain in a tight loop (without `next`'s bookkeeping) until `f` breaks
+    ///
+    /// Cursor is left right after the element that broke the loop
+    #[inline]
+    fn try_walk<'a, B, F>(&mut self, mut f: F) -> Option<B>
+    where
+        T: 'a,
+        F: FnMut(&'a T) -> ControlFlow<B>,
+    {
+        trace!("try_walk()");
+        while let Some(ptr) = self.current {
+            // We can deref its pointer because `inner` owns it and we own `inner`
+            // The caller ties `'a` to the borrow of the iterator (like `next` does)
+            let node: &'a Node<T> = unsafe { ptr.as_ref() };
+            let value = &node.values()[self.offset];
+            self.advance(node);
+            if let ControlFlow::Break(b) = f(value) {
+                return Some(b);
+            }
+        }
+        None
+    }
+
+    /// Moves past the value at `offset` of `node` (the current node), going to the next node after its last value
+    #[inline]
+    fn advance(&mut self, node: &Node<T>) {
+        self.index += 1;
+        self.offset += 1;
+        if self.offset == node.values().len() {
+            self.offset = 0;
+            self.current = node.next().map(NonNull::from);
+        }
+    }
+
+    /// Returns `Inner` referenced by the iterator
+    #[cfg(feature = "serde-traits")]
+    #[inline]
+    pub(crate) fn inner(&self) -> &Arc<Inner<T>> {
+        &self.inner
+    }
+
     /// Returns reference to last element in list
     ///
-    /// `Relaxed` ordering is used to extract the `last_node`, so you shouldn't depend on this being sequentially consistent, this is more of a helper than something you should depend on
+    /// `Acquire` ordering is used to extract the `last_node`, so you shouldn't depend on this being sequentially consistent, this is more of a helper than something you should depend on
     ///
     /// ```rust
     /// # use voluntary_servitude::vs;
@@ -90,21 +146,25 @@ impl<T> Iter<T> {
     /// assert_eq!(iter.last_node(), Some(&4));
     /// ```
     #[inline]
-    pub fn last_node<'a>(&'a self) -> Option<&'a T> {
+    pub fn last_node(&self) -> Option<&T> {
         trace!("last_node()");
         // We can deref its pointer because `inner` owns it and we own `inner`
         // We need to hack around the borrow checker to "prove" that
         // the ref extracted from `NonNull` has the same lifetime as `&self`
         self.inner
             .last_node()
-            .map(|nn| unsafe { (*nn.as_ptr()).value() })
+            .and_then(|nn| unsafe { nn.as_ref() }.values().last())
     }
 
     /// Returns current iterator size (may grow, but not decrease, be careful with race-conditions)
     ///
+    /// It's the total length, including the elements already yielded, use [`remaining`] for the elements left to yield
+    ///
     /// If `Iter` was originally empty or was already consumed it will not grow (`FusedIterator`)
     ///
-    /// `Relaxed` ordering is used to extract the length, so you shouldn't depend on this being sequentially consistent, only atomic
+    /// `Acquire` ordering is used to extract the length, so you shouldn't depend on this being sequentially consistent, only atomic
+    ///
+    /// [`remaining`]: #method.remaining
     ///
     /// ```rust
     /// # use voluntary_servitude::vs;
@@ -135,7 +195,7 @@ impl<T> Iter<T> {
 
     /// Checks if iterator's length is empty (will return `None` on `next`)
     ///
-    /// `Relaxed` ordering is used to extract the length, so you shouldn't depend on this being sequentially consistent, only atomic
+    /// `Acquire` ordering is used to extract the length, so you shouldn't depend on this being sequentially consistent, only atomic
     ///
     /// ```rust
     /// # use voluntary_servitude::vs;
@@ -161,7 +221,30 @@ impl<T> Iter<T> {
     #[inline]
     pub fn is_empty(&self) -> bool {
         trace!("is_empty()");
-        self.current.map_or(true, |_| self.len() == 0)
+        self.current.is_none_or(|_| self.len() == 0)
+    }
+
+    /// Returns number of elements left to yield (may grow, be careful with race-conditions)
+    ///
+    /// As opposed to [`len`], which includes the elements already yielded
+    ///
+    /// [`len`]: #method.len
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let vs = vs![3, 4];
+    /// let mut iter = &mut vs.iter();
+    /// assert_eq!(iter.remaining(), 2);
+    ///
+    /// assert_eq!(iter.next(), Some(&3));
+    /// assert_eq!(iter.remaining(), 1);
+    /// assert_eq!(iter.len(), 2);
+    /// ```
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        trace!("remaining()");
+        self.len().saturating_sub(self.index)
     }
 
     /// Obtains current iterator index
@@ -188,6 +271,167 @@ impl<T> Iter<T> {
     }
 }
 
+impl<T> Iter<T> {
+    /// Yields the next elements with their absolute index in the list (unlike `enumerate`, which counts from zero)
+    ///
+    /// Borrows the iterator, so it keeps its position afterwards (`Iter` only iterates by mutable reference)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let vs = vs!['a', 'b', 'c'];
+    /// let mut iter = vs.iter();
+    /// assert_eq!((&mut iter).next(), Some(&'a'));
+    /// assert_eq!(iter.indexed().collect::<Vec<_>>(), vec![(1, &'b'), (2, &'c')]);
+    /// ```
+    #[inline]
+    pub fn indexed(&mut self) -> impl Iterator<Item = (usize, &T)> {
+        trace!("indexed()");
+        let mut iter = self;
+        from_fn(move || {
+            let index = iter.index;
+            iter.next().map(|value| (index, value))
+        })
+    }
+
+    /// Groups the next consecutive elements that share the same key (like run-length encoding)
+    ///
+    /// Borrows the iterator (like [`indexed`]), an element is only consumed when it joins a group
+    ///
+    /// [`indexed`]: #method.indexed
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let vs = vs!["a", "b", "cd", "e", "fg"];
+    /// let mut iter = vs.iter();
+    /// let groups: Vec<_> = iter.group_adjacent_by(|s| s.len()).collect();
+    /// assert_eq!(groups, vec![vec![&"a", &"b"], vec![&"cd"], vec![&"e"], vec![&"fg"]]);
+    /// ```
+    #[inline]
+    pub fn group_adjacent_by<K: PartialEq, F: FnMut(&T) -> K>(
+        &mut self,
+        mut f: F,
+    ) -> impl Iterator<Item = Vec<&T>> {
+        trace!("group_adjacent_by()");
+        let mut iter = self;
+        // Key of the peeked element that ended the last group, so `f` runs once per element
+        let mut pending = None;
+        from_fn(move || {
+            let first = iter.next()?;
+            let key = pending.take().unwrap_or_else(|| f(first));
+            let mut group = vec![first];
+            while let Some(value) = iter.peek() {
+                let next_key = f(value);
+                if next_key != key {
+                    pending = Some(next_key);
+                    break;
+                }
+                group.extend(iter.next());
+            }
+            Some(group)
+        })
+    }
+
+    /// Gets the next element without advancing
+    #[inline]
+    fn peek(&self) -> Option<&T> {
+        // We can deref its pointer because `inner` owns it and we own `inner`
+        let node = self.current.map(|ptr| unsafe { ptr.as_ref() })?;
+        Some(&node.values()[self.offset])
+    }
+
+    /// Consumes the iterator, yielding the owned values returned by `f` for the next elements (skipping `None`s)
+    ///
+    /// Like `filter_map`, but as the values are owned it doesn't need to borrow the iterator mutably
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let vs = vs!["1", "a", "3"];
+    /// let numbers: Vec<u8> = vs.iter().filter_map_cloned(|s| s.parse().ok()).collect();
+    /// assert_eq!(numbers, vec![1, 3]);
+    /// ```
+    #[inline]
+    pub fn filter_map_cloned<U, F: FnMut(&T) -> Option<U>>(
+        self,
+        mut f: F,
+    ) -> impl Iterator<Item = U> {
+        trace!("filter_map_cloned()");
+        let mut iter = self;
+        from_fn(move || (&mut iter).find_map(&mut f))
+    }
+}
+
+impl<T: Clone> Iter<T> {
+    /// Clones up to `n` of the next elements, advancing past them (stops early at the end, so it can be called repeatedly to consume in batches)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let vs = vs![1, 2, 3];
+    /// let mut iter = vs.iter();
+    /// assert_eq!(iter.take_n_cloned(2), vec![1, 2]);
+    /// assert_eq!(iter.take_n_cloned(2), vec![3]);
+    /// assert!(iter.take_n_cloned(2).is_empty());
+    /// ```
+    #[inline]
+    pub fn take_n_cloned(&mut self, n: usize) -> Vec<T> {
+        trace!("take_n_cloned({})", n);
+        self.take(n).cloned().collect()
+    }
+}
+
+impl<T> VoluntaryServitude<T> {
+    /// Makes iterator that keeps following the list as it grows ("tail -f" of the list)
+    ///
+    /// [`FollowIter`] returns `None` when it reaches the end, but it isn't fused: it continues from there once new elements are appended
+    ///
+    /// [`FollowIter`]: ./struct.FollowIter.html
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1];
+    /// let mut follow = list.follow();
+    /// assert_eq!((&mut follow).collect::<Vec<_>>(), vec![&1]);
+    ///
+    /// list.append(2);
+    /// assert_eq!((&mut follow).collect::<Vec<_>>(), vec![&2]);
+    /// ```
+    #[inline]
+    pub fn follow(&self) -> FollowIter<T> {
+        debug!("follow()");
+        FollowIter::from(self.snapshot_arc())
+    }
+}
+
+impl<T> VoluntaryServitude<Weak<T>> {
+    /// Makes lock-free iterator that upgrades each `Weak<T>`, skipping the ones already dropped
+    ///
+    /// Dropped entries are not removed from the list, they stay as tombstones
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// use std::sync::Arc;
+    /// let (a, b) = (Arc::new(1), Arc::new(2));
+    /// let list = vs![];
+    /// list.append_weak(&a);
+    /// list.append_weak(&b);
+    ///
+    /// drop(a);
+    /// assert_eq!(list.live_iter().collect::<Vec<_>>(), vec![Arc::new(2)]);
+    /// assert_eq!(list.len(), 2);
+    /// ```
+    #[inline]
+    pub fn live_iter(&self) -> impl Iterator<Item = Arc<T>> {
+        trace!("live_iter()");
+        let mut iter = self.iter();
+        from_fn(move || (&mut iter).find_map(Weak::upgrade))
+    }
+}
+
 impl<'a, T> Iterator for &'a mut Iter<T> {
     type Item = &'a T;
 
@@ -199,27 +443,101 @@ impl<'a, T> Iterator for &'a mut Iter<T> {
         // We need to hack around the borrow checker to "prove" that
         // the ref extracted from `NonNull` has the same lifetime as `&self`
         let data = if let Some(ptr) = self.current {
-            self.index += 1;
-            Some(unsafe { (*ptr.as_ptr()).value() })
+            let node: &'a Node<T> = unsafe { ptr.as_ref() };
+            let value = &node.values()[self.offset];
+            self.advance(node);
+            Some(value)
         } else {
             None
         };
 
         debug!("{} at {} of {}", data.is_some(), self.index, self.len());
         debug_assert!(
-            self.is_empty() && self.index == 0 && data.is_none() || self.inner.len() != 0
+            self.is_empty() && self.index == 0 && data.is_none() || !self.inner.is_empty()
         );
         debug_assert!((self.index <= self.len() && data.is_some()) || self.index >= self.len());
         debug_assert!((self.index > self.len() && data.is_none()) || self.index <= self.len());
+        data
+    }
 
+    /// Jumps straight to the last node instead of walking the chain, consuming the iterator
+    ///
+    /// Returns `None` if the iterator was empty or already consumed (as `next` would)
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        trace!("last()");
+        let _ = self.current.take()?;
+        self.index = self.inner.len();
         // We can deref its pointer because `inner` owns it and we own `inner`
         // We need to hack around the borrow checker to "prove" that
         // the ref extracted from `NonNull` has the same lifetime as `&self`
-        self.current = self
-            .current
-            .and_then(|n| unsafe { (*n.as_ptr()).next() })
-            .and_then(|n| NonNull::new(n as *const Node<T> as *mut Node<T>));
-        data
+        self.inner
+            .last_node()
+            .and_then(|nn| unsafe { nn.as_ref() }.values().last())
+    }
+
+    #[inline]
+    fn find<P>(&mut self, mut predicate: P) -> Option<Self::Item>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        trace!("find()");
+        self.try_walk(|value| {
+            if predicate(&value) {
+                ControlFlow::Break(value)
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+    }
+
+    #[inline]
+    fn position<P>(&mut self, mut predicate: P) -> Option<usize>
+    where
+        P: FnMut(Self::Item) -> bool,
+    {
+        trace!("position()");
+        let mut position = 0;
+        self.try_walk(|value| {
+            if predicate(value) {
+                ControlFlow::Break(position)
+            } else {
+                position += 1;
+                ControlFlow::Continue(())
+            }
+        })
+    }
+
+    #[inline]
+    fn any<F>(&mut self, mut f: F) -> bool
+    where
+        F: FnMut(Self::Item) -> bool,
+    {
+        trace!("any()");
+        self.try_walk(|value| {
+            if f(value) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .is_some()
+    }
+
+    #[inline]
+    fn all<F>(&mut self, mut f: F) -> bool
+    where
+        F: FnMut(Self::Item) -> bool,
+    {
+        trace!("all()");
+        self.try_walk(|value| {
+            if f(value) {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        })
+        .is_none()
     }
 
     #[inline]
@@ -229,11 +547,208 @@ impl<'a, T> Iterator for &'a mut Iter<T> {
     }
 }
 
-impl<'a, T> FusedIterator for &'a mut Iter<T> {}
+impl<T> FusedIterator for &mut Iter<T> {}
+
+/// Iterator that follows [`VS`] as it grows, created by [`follow`]
+///
+/// It never blocks: `next` returns `None` when there are no new elements, but it can be called again later to get the ones appended meanwhile (it's not a `FusedIterator`)
+///
+/// It follows the chain `VS` had when it was created, so after `VS` is cleared (or swapped) it doesn't see new elements anymore
+///
+/// Like [`Iter`], `Iterator` is implemented for `&mut FollowIter<T>`
+///
+/// [`VS`]: ./type.VS.html
+/// [`follow`]: ./struct.VoluntaryServitude.html#method.follow
+/// [`Iter`]: ./struct.Iter.html
+pub struct FollowIter<T> {
+    /// References `Inner` extracted from `VS`
+    inner: Arc<Inner<T>>,
+    /// Node being read (`None` if nothing was yielded yet)
+    current: Option<NonNull<Node<T>>>,
+    /// Offset of the next value inside current node
+    offset: usize,
+    /// Number of elements yielded
+    index: usize,
+}
+
+// `FollowIter` only gives shared references to values owned by `inner`, just like `Arc<Inner<T>>`
+unsafe impl<T: Send + Sync> Send for FollowIter<T> {}
+unsafe impl<T: Send + Sync> Sync for FollowIter<T> {}
+
+impl<T: Debug> Debug for FollowIter<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // We can deref its pointer because `inner` owns it and we own `inner`
+        let current = self.current.as_ref().map(|ptr| unsafe { ptr.as_ref() });
+        f.debug_struct("FollowIter")
+            .field("inner", &self.inner)
+            .field("current", &current)
+            .field("offset", &self.offset)
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl<T> From<Arc<Inner<T>>> for FollowIter<T> {
+    #[inline]
+    fn from(inner: Arc<Inner<T>>) -> Self {
+        trace!("From<Arc<Inner<T>>>");
+        Self {
+            inner,
+            current: None,
+            offset: 0,
+            index: 0,
+        }
+    }
+}
+
+impl<T> FollowIter<T> {
+    /// Obtains number of elements yielded so far
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2];
+    /// let mut follow = list.follow();
+    /// assert_eq!((&mut follow).count(), 2);
+    /// assert_eq!(follow.index(), 2);
+    /// ```
+    #[inline]
+    pub fn index(&self) -> usize {
+        trace!("index() = {}", self.index);
+        self.index
+    }
+}
+
+impl<'a, T> Iterator for &'a mut FollowIter<T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        trace!("next()");
+        // We can deref its pointer because `inner` owns it and we own `inner`
+        // We need to hack around the borrow checker to "prove" that
+        // the ref extracted from `NonNull` has the same lifetime as `&self`
+        let mut node: &'a Node<T> = match self.current {
+            Some(nn) => unsafe { nn.as_ref() },
+            None => unsafe { self.inner.first_node()?.as_ref() },
+        };
+        // Values of a linked node never change, so only `next` must be checked for growth
+        if self.offset == node.values().len() {
+            node = node.next()?;
+            self.offset = 0;
+        }
+        self.current = Some(NonNull::from(node));
+        self.offset += 1;
+        self.index += 1;
+        Some(&node.values()[self.offset - 1])
+    }
+}
 
 #[cfg(test)]
 mod tests {
+    use super::Iter;
     use crate::{setup_logger, voluntary_servitude::VS};
+    use std::sync::Arc;
+
+    #[test]
+    fn filter_map_cloned() {
+        setup_logger();
+        let vs: VS<_> = (0..20).collect();
+        let half = |n: &u32| {
+            if n.is_multiple_of(2) {
+                Some(n.to_string())
+            } else {
+                None
+            }
+        };
+        let expected: Vec<_> = (&mut vs.iter()).filter_map(half).collect();
+        let owned: Vec<_> = vs.iter().filter_map_cloned(half).collect();
+        assert_eq!(owned, expected);
+        assert_eq!(owned.len(), 10);
+
+        let mut iter = vs.iter();
+        let _ = (&mut iter).nth(15);
+        let rest: Vec<_> = iter.filter_map_cloned(|n| Some(n * 2)).collect();
+        assert_eq!(rest, vec![32, 34, 36, 38]);
+        assert_eq!(vs.iter().filter_map_cloned(|_| None::<u8>).count(), 0);
+    }
+
+    #[test]
+    fn group_adjacent_by() {
+        setup_logger();
+        let vs = vs![1, 1, 2, 2, 2, 1];
+        let mut iter = vs.iter();
+        let groups: Vec<_> = iter.group_adjacent_by(|n| *n).collect();
+        assert_eq!(groups, vec![vec![&1, &1], vec![&2, &2, &2], vec![&1]]);
+
+        // Elements after the groups taken are kept
+        let mut iter = vs.iter();
+        assert_eq!(iter.group_adjacent_by(|n| *n).next(), Some(vec![&1, &1]));
+        assert_eq!(iter.index(), 2);
+        assert_eq!((&mut iter).next(), Some(&2));
+
+        vs.append(1);
+        let groups: Vec<_> = iter.group_adjacent_by(|n| n % 2).collect();
+        assert_eq!(groups, vec![vec![&2, &2], vec![&1, &1]]);
+        assert_eq!(iter.group_adjacent_by(|n| *n).count(), 0);
+
+        // The key is computed once per element
+        let mut calls = 0;
+        let groups = vs
+            .iter()
+            .group_adjacent_by(|n| {
+                calls += 1;
+                *n
+            })
+            .count();
+        assert_eq!((groups, calls), (3, vs.len()));
+    }
+
+    #[test]
+    fn indexed() {
+        setup_logger();
+        let vs: VS<_> = (0..10).map(|n| n * 10).collect();
+        for offset in 0..=10 {
+            let mut iter = Iter::iter_from(vs.snapshot_arc(), offset);
+            let indexed: Vec<_> = iter.indexed().collect();
+            let expected: Vec<_> = (offset..10).map(|n| (n, n * 10)).collect();
+            assert_eq!(
+                indexed,
+                expected.iter().map(|(i, v)| (*i, v)).collect::<Vec<_>>()
+            );
+            assert_eq!(iter.index(), 10);
+        }
+
+        let mut iter = vs.iter();
+        assert_eq!(iter.indexed().nth(3), Some((3, &30)));
+        assert_eq!(iter.indexed().next(), Some((4, &40)));
+        vs.append(100);
+        assert_eq!(iter.indexed().last(), Some((10, &100)));
+    }
+
+    #[test]
+    fn take_n_cloned() {
+        setup_logger();
+        let vs = vs![1, 2, 3, 4, 5];
+        let mut iter = vs.iter();
+        let mut batches = Vec::new();
+        loop {
+            let batch = iter.take_n_cloned(2);
+            if batch.is_empty() {
+                break;
+            }
+            batches.push(batch);
+        }
+        assert_eq!(batches, vec![vec![1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(iter.index(), 5);
+
+        vs.append(6);
+        let mut iter = vs.iter();
+        assert!(iter.take_n_cloned(0).is_empty());
+        assert_eq!(iter.index(), 0);
+        assert_eq!(iter.take_n_cloned(10), vec![1, 2, 3, 4, 5, 6]);
+    }
 
     #[test]
     fn iter_all() {
@@ -341,18 +856,147 @@ mod tests {
         assert_eq!(iter.index(), iter.len());
     }
 
+    #[test]
+    fn iter_last() {
+        setup_logger();
+        let vs = vs![1, 2, 3];
+        assert_eq!(vs.iter().last(), Some(&3));
+
+        let mut iter = vs.iter();
+        assert_eq!((&mut iter).next(), Some(&1));
+        assert_eq!((&mut iter).last(), Some(&3));
+        assert_eq!(iter.index(), 3);
+        assert_eq!((&mut iter).next(), None);
+        assert_eq!((&mut iter).last(), None);
+
+        let mut iter = vs.iter();
+        assert_eq!((&mut iter).count(), 3);
+        vs.append(4);
+        assert_eq!((&mut iter).last(), None);
+
+        let vs: VS<()> = vs![];
+        let iter = vs.iter();
+        vs.append(());
+        assert_eq!(iter.clone().last(), None);
+        assert_eq!(vs.iter().last(), Some(&()));
+    }
+
+    #[test]
+    fn iter_short_circuit() {
+        setup_logger();
+        let vs = vs![1, 2, 3, 4, 5];
+        let mut iter = vs.iter();
+        assert_eq!((&mut iter).find(|&&v| v == 3), Some(&3));
+        assert_eq!(iter.index(), 3);
+        assert_eq!((&mut iter).next(), Some(&4));
+        assert_eq!((&mut iter).find(|&&v| v == 3), None);
+        assert_eq!(iter.index(), 5);
+
+        let mut iter = vs.iter();
+        assert_eq!((&mut iter).position(|&v| v == 2), Some(1));
+        assert!((&mut iter).any(|&v| v == 4));
+        assert_eq!((&mut iter).next(), Some(&5));
+
+        assert!((&mut vs.iter()).all(|&v| v > 0));
+        assert!(!(&mut vs.iter()).all(|&v| v < 3));
+        assert!(!(&mut vs.iter()).any(|&v| v > 5));
+        assert_eq!((&mut vs.iter()).position(|&v| v > 5), None);
+    }
+
+    #[test]
+    fn iter_remaining() {
+        setup_logger();
+        let vs = vs![1, 2, 3];
+        let mut iter = vs.iter();
+        for remaining in (0..3).rev() {
+            assert!((&mut iter).next().is_some());
+            assert_eq!(iter.remaining(), remaining);
+            assert_eq!(iter.len(), 3);
+        }
+        assert_eq!((&mut iter).next(), None);
+        assert_eq!(iter.remaining(), 0);
+
+        let iter = vs.iter();
+        vs.append(4);
+        assert_eq!(iter.remaining(), 4);
+        assert_eq!(VS::<()>::new().iter().remaining(), 0);
+    }
+
+    #[test]
+    fn follow_stages() {
+        setup_logger();
+        let vs = vs![];
+        let mut follow = vs.follow();
+        assert_eq!((&mut follow).next(), None);
+
+        for stage in 0..3 {
+            vs.extend(stage * 10..stage * 10 + 3);
+            let expected: Vec<_> = (stage * 10..stage * 10 + 3).collect();
+            assert_eq!((&mut follow).cloned().collect::<Vec<_>>(), expected);
+            assert_eq!((&mut follow).next(), None);
+        }
+        assert_eq!(follow.index(), 9);
+
+        vs.clear();
+        vs.append(100);
+        assert_eq!((&mut follow).next(), None);
+    }
+
+    #[test]
+    fn follow_producer() {
+        use std::{sync::Arc, thread::spawn};
+        setup_logger();
+        let vs = Arc::new(vs![]);
+        let mut follow = vs.follow();
+        let producer = {
+            let vs = Arc::clone(&vs);
+            spawn(move || (0..1000).for_each(|i| vs.append(i)))
+        };
+
+        let mut expected = 0;
+        while expected < 1000 {
+            for &value in &mut follow {
+                assert_eq!(value, expected);
+                expected += 1;
+            }
+        }
+        producer.join().unwrap();
+        assert_eq!((&mut follow).next(), None);
+    }
+
+    #[test]
+    fn follow_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<super::FollowIter<()>>();
+    }
+
+    #[test]
+    fn live_iter_skips_dropped() {
+        setup_logger();
+        let subscribers: Vec<_> = (0..5).map(Arc::new).collect();
+        let vs = vs![];
+        for subscriber in &subscribers {
+            vs.append_weak(subscriber);
+        }
+
+        let alive: Vec<_> = subscribers.into_iter().filter(|s| **s % 2 == 0).collect();
+        assert_eq!(vs.live_iter().collect::<Vec<_>>(), alive);
+        assert_eq!(vs.len(), 5);
+        assert_eq!(vs.iter().filter(|w| w.upgrade().is_none()).count(), 2);
+    }
+
     #[test]
     fn iter_drop() {
         setup_logger();
         let vs = vs![1, 2, 3, 4, 5];
         drop(vs.iter());
 
-        let mut iter = &mut vs.iter();
-        assert_eq!(iter.next(), Some(&1));
+        let mut iter = vs.iter();
+        assert_eq!((&mut iter).next(), Some(&1));
         drop(iter);
 
-        let mut iter = &mut vs.iter();
-        while iter.next().is_some() {}
+        let mut iter = vs.iter();
+        while (&mut iter).next().is_some() {}
         drop(iter);
     }
 
@@ -360,21 +1004,21 @@ mod tests {
     fn iter_drop_many() {
         setup_logger();
         let vs = vs![1, 2, 3, 4, 5];
-        let iter = &mut vs.iter();
-        let mut iter1 = &mut vs.iter();
-        let mut iter2 = &mut vs.iter();
-        assert_eq!(iter2.next(), Some(&1));
-        assert_eq!(iter2.next(), Some(&2));
-        let mut iter3 = &mut vs.iter();
-        assert_eq!(iter2.next(), Some(&3));
-        assert_eq!(iter2.next(), Some(&4));
-        assert_eq!(iter2.next(), Some(&5));
+        let iter = vs.iter();
+        let mut iter1 = vs.iter();
+        let mut iter2 = vs.iter();
+        assert_eq!((&mut iter2).next(), Some(&1));
+        assert_eq!((&mut iter2).next(), Some(&2));
+        let mut iter3 = vs.iter();
+        assert_eq!((&mut iter2).next(), Some(&3));
+        assert_eq!((&mut iter2).next(), Some(&4));
+        assert_eq!((&mut iter2).next(), Some(&5));
         drop(iter2);
-        assert_eq!(iter1.next(), Some(&1));
+        assert_eq!((&mut iter1).next(), Some(&1));
         drop(iter);
         drop(iter1);
-        assert_eq!(iter3.next(), Some(&1));
-        assert_eq!(iter3.next(), Some(&2));
+        assert_eq!((&mut iter3).next(), Some(&1));
+        assert_eq!((&mut iter3).next(), Some(&2));
         drop(iter3);
     }
 }
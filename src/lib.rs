@@ -3,6 +3,7 @@
 //! # Features
 //!  - [`Atomic abstractions (Atomic, AtomicOption, FillOnceAtomicOption, FillOnceAtomicArc)`]
 //!  - [`Thread-safe appendable list with a lock-free iterator (VoluntaryServitude - also called VS)`]
+//!  - [`Lock-free stack (Stack)`]
 //!  - [`Serde serialization/deserialization ("serde-traits" feature)`]
 //!  - [`par_extend, from_par_iter rayon implementation ("rayon-traits" feature)`]
 //!  - [`Logging ("logs" feature)`]
@@ -14,22 +15,38 @@
 //!  - [`AtomicOption`] -> atomic `Option<Box<T>>`
 //!  - [`FillOnceAtomicOption`] -> atomic `Option<Box<T>>` that can give references (ideal for iterators)
 //!  - [`FillOnceAtomicArc`] -> atomic `Option<Arc<T>>` with a limited API (like [`FillOnceAtomicOption`])
+//!  - [`ThinAtomicOption`] -> atomic `Option<Box<T>>` for unsized types (like `dyn Fn()`), with an extra indirection
 //!
 //! With [`Atomic`] and [`AtomicOption`] it's not safe to get a reference, you must replace the value to access it.
 //!
 //! To safely get a reference of T you must use [`FillOnceAtomicOption`] and accept the API limitations (initially `None` but can be filled once).
 //!
+//! With the `blocking` feature [`AtomicOption`] and [`FillOnceAtomicOption`] can also block until a notifying store fills them (`wait_take`/`wait_filled`).
+//!
 //! For a safe `AtomicArc` you must use some data-structure from `arc-swap`, `RwLock/Mutex` from `parking_lot` (or `std`, which is slower but the standard) or [`FillOnceAtomicArc`] and accept the limited API (2018).
 //!
 //! # Thread-safe appendable list that can create a lock-free iterator
 //!  - [`VoluntaryServitude`] (also called [`VS`])
+//!  - [`VSBuilder`]
+//!  - [`UniqueVS`]
+//!  - [`concat`]
+//!
+//! # Lock-free stack
+//!  - [`Stack`]
 //!
 //! # API of `VS` Iterator
 //! - [`Iter`]
+//! - [`FollowIter`]
+//!
+//! # API of `VS` append batch
+//! - [`Batch`]
+//!
+//! # API of `VS` immutable snapshot
+//! - [`Frozen`]
 //!
 //! # Logging
 //!
-//! *Setup logger according to `RUST_LOG` env var and `logs` feature*
+//! *Setup logger according to `RUST_LOG` env var and `logs` feature (or `tracing` feature)*
 //!
 //! ## Enable the feature:
 //!
@@ -57,12 +74,26 @@
 //! // ...
 //! ```
 //!
+//! ## Or emit `tracing` events instead
+//!
+//! With the `tracing` feature the same messages are emitted as `tracing` events (install a subscriber to collect them)
+//!
+//! If `logs` is also enabled they are forwarded to `log` when no subscriber is set
+//!
+//! **Cargo.toml**
+//! ```toml
+//! [dependencies]
+//! voluntary_servitude = { version = "4", features = "tracing" }
+//! ```
+//!
 //! [`Atomic`]: ./atomics/struct.Atomic.html
 //! [`AtomicOption`]: ./atomics/struct.AtomicOption.html
 //! [`FillOnceAtomicOption`]: ./atomics/struct.FillOnceAtomicOption.html
 //! [`FillOnceAtomicArc`]: ./atomics/struct.FillOnceAtomicArc.html
+//! [`ThinAtomicOption`]: ./atomics/struct.ThinAtomicOption.html
 //! [`Atomic abstractions (Atomic, AtomicOption, FillOnceAtomicOption, FillOnceAtomicArc)`]: #atomic-abstractions
 //! [`Thread-safe appendable list with a lock-free iterator (VoluntaryServitude - also called VS)`]: ./struct.VoluntaryServitude.html
+//! [`Lock-free stack (Stack)`]: ./struct.Stack.html
 //! [`Serde serialization/deserialization ("serde-traits" feature)`]: ./struct.VoluntaryServitude.html#impl-Serialize
 //! [`&VS`]: ./struct.VoluntaryServitude.html#impl-Insertable<Tab>
 //! [`&Iter`]: ./struct.Iter.html#impl-Insertable<Tab>
@@ -70,6 +101,12 @@
 //! [`VoluntaryServitude`]: ./struct.VoluntaryServitude.html
 //! [`VS`]: ./type.VS.html
 //! [`Iter`]: ./struct.Iter.html
+//! [`FollowIter`]: ./struct.FollowIter.html
+//! [`VSBuilder`]: ./struct.VSBuilder.html
+//! [`UniqueVS`]: ./struct.UniqueVS.html
+//! [`concat`]: ./fn.concat.html
+//! [`Batch`]: ./struct.Batch.html
+//! [`Frozen`]: ./struct.Frozen.html
 //! [`Logging ("logs" feature)`]: #logging
 
 #![deny(
@@ -81,20 +118,14 @@
     unused_qualifications,
     unused_results,
     bad_style,
-    const_err,
     dead_code,
     improper_ctypes,
-    legacy_directory_ownership,
     non_shorthand_field_patterns,
     no_mangle_generic_items,
     overflowing_literals,
     path_statements,
     patterns_in_fns_without_body,
-    plugin_as_library,
-    private_in_public,
-    safe_extern_statics,
     unconditional_recursion,
-    unions_with_drop_fields,
     unused_allocation,
     unused_comparisons,
     unused_parens,
@@ -135,7 +166,7 @@ macro_rules! vs {
 ///
 /// ```
 /// # env_logger::init();
-/// # use voluntary_servitude::vs;
+/// # use voluntary_servitude::voluntary_servitude;
 /// use voluntary_servitude::VS;
 /// let vs: VS<()> = voluntary_servitude![];
 /// assert!(vs.is_empty());
@@ -167,7 +198,7 @@ macro_rules! voluntary_servitude {
 
 /// Remove logging macros when they are disabled (at compile time)
 #[macro_use]
-#[cfg(not(feature = "logs"))]
+#[cfg(not(any(feature = "logs", feature = "tracing")))]
 #[allow(unused)]
 mod mock {
     macro_rules! trace(($($x:tt)*) => ());
@@ -178,8 +209,11 @@ mod mock {
 }
 
 pub mod atomics;
+mod batch;
+mod frozen;
 mod iterator;
 mod node;
+mod stack;
 mod traits;
 mod voluntary_servitude;
 
@@ -187,10 +221,12 @@ mod voluntary_servitude;
 #[allow(unused)]
 mod prelude {
     pub(crate) use crate::atomics::{Atomic, AtomicOption, FillOnceAtomicOption};
-    pub(crate) use crate::{IntoPtr, NotEmpty};
-    pub(crate) use crate::{Iter, VoluntaryServitude, VS};
-    #[cfg(feature = "logs")]
+    pub(crate) use crate::{Batch, Frozen, Iter, VoluntaryServitude, VS};
+    pub(crate) use crate::{IntegrityError, IntoPtr, NotEmpty};
+    #[cfg(all(feature = "logs", not(feature = "tracing")))]
     pub use log::{debug, error, info, trace, warn};
+    #[cfg(feature = "tracing")]
+    pub use tracing::{debug, error, info, trace, warn};
 }
 
 use std::{error::Error, fmt, fmt::Debug, fmt::Display, fmt::Formatter};
@@ -219,8 +255,58 @@ impl Display for NotEmpty {
 
 impl Error for NotEmpty {}
 
-pub use crate::iterator::Iter;
-pub use crate::voluntary_servitude::{VoluntaryServitude, VS};
+/// Inconsistency found by [`verify_integrity`] while walking a [`VS`]'s chain
+///
+/// [`verify_integrity`]: ./struct.VoluntaryServitude.html#method.verify_integrity
+/// [`VS`]: ./type.VS.html
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum IntegrityError {
+    /// Chain has more elements than its size (the walk stopped there, it may be a cycle)
+    TooLong {
+        /// Size stored in the list
+        size: usize,
+    },
+    /// Chain has fewer elements than its size
+    TooShort {
+        /// Size stored in the list
+        size: usize,
+        /// Elements found in the chain
+        counted: usize,
+    },
+    /// Last node found in the chain isn't the one stored as last
+    LastNodeMismatch {
+        /// Nodes walked
+        nodes: usize,
+    },
+}
+
+impl Display for IntegrityError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            IntegrityError::TooLong { size } => {
+                write!(f, "chain has more than {} elements", size)
+            }
+            IntegrityError::TooShort { size, counted } => {
+                write!(f, "chain has {} elements, expected {}", counted, size)
+            }
+            IntegrityError::LastNodeMismatch { nodes } => {
+                write!(f, "last of {} nodes isn't the stored last node", nodes)
+            }
+        }
+    }
+}
+
+impl Error for IntegrityError {}
+
+pub use crate::batch::Batch;
+pub use crate::frozen::Frozen;
+pub use crate::iterator::{FollowIter, Iter};
+pub use crate::stack::Stack;
+pub use crate::voluntary_servitude::{concat, Inner, UniqueVS, VSBuilder, VoluntaryServitude, VS};
+
+#[cfg(feature = "serde-traits")]
+pub use crate::traits::{deserialize_bounded, serde_bytes};
 
 use std::ptr::null_mut;
 
@@ -232,7 +318,6 @@ pub(crate) trait IntoPtr<T> {
 
 impl<T> IntoPtr<T> for T {
     #[inline]
-    #[must_use]
     fn into_ptr(self) -> *mut Self {
         Box::into_raw(Box::new(self))
     }
@@ -240,7 +325,6 @@ impl<T> IntoPtr<T> for T {
 
 impl<T> IntoPtr<T> for Option<T> {
     #[inline]
-    #[must_use]
     fn into_ptr(self) -> *mut T {
         self.map(Box::new).into_ptr()
     }
@@ -248,7 +332,6 @@ impl<T> IntoPtr<T> for Option<T> {
 
 impl<T> IntoPtr<T> for Box<T> {
     #[inline]
-    #[must_use]
     fn into_ptr(self) -> *mut T {
         Self::into_raw(self)
     }
@@ -256,12 +339,12 @@ impl<T> IntoPtr<T> for Box<T> {
 
 impl<T> IntoPtr<T> for Option<Box<T>> {
     #[inline]
-    #[must_use]
     fn into_ptr(self) -> *mut T {
         self.map_or(null_mut(), Box::into_raw)
     }
 }
 
+/// Initializes logger once for all tests
 #[cfg(test)]
 pub fn setup_logger() {
     use std::sync::Once;
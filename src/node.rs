@@ -4,57 +4,185 @@
 
 use crate::prelude::*;
 use std::fmt::{self, Debug, Formatter};
-use std::sync::atomic::Ordering;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::{mem::drop, mem::replace, mem::take, mem::ManuallyDrop, ptr::read, ptr::NonNull};
+use std::{slice::from_mut, slice::from_ref, sync::atomic::Ordering};
 
-/// One [`VoluntaryServitude`] element
+/// Values stored in a [`Node`]
+///
+/// [`Node`]: ./struct.Node.html
+enum Values<T> {
+    /// Value appended individually
+    One(T),
+    /// Values appended in bulk (never empty)
+    Many(Box<[T]>),
+}
+
+/// One or more [`VoluntaryServitude`] elements (bulk appends are stored in a single node)
+///
+/// Values never change after the node is linked (unless there is exclusive access to the chain)
 ///
 /// [`VoluntaryServitude`]: ./struct.VoluntaryServitude.html
 pub struct Node<T> {
-    /// Inner value
-    value: T,
+    /// Inner values
+    values: Values<T>,
     /// Next node in chain
     next: FillOnceAtomicOption<Node<T>>,
 }
 
 impl<T> Node<T> {
-    /// Returns reference to inner value
+    /// Returns reference to inner values (never empty)
+    #[inline]
+    pub fn values(&self) -> &[T] {
+        match &self.values {
+            Values::One(value) => from_ref(value),
+            Values::Many(values) => values,
+        }
+    }
+
+    /// Returns mutable reference to inner values (never empty)
     #[inline]
-    pub fn value(&self) -> &T {
-        trace!("value() = {:p}", &self.value);
-        &self.value
+    pub fn values_mut(&mut self) -> &mut [T] {
+        match &mut self.values {
+            Values::One(value) => from_mut(value),
+            Values::Many(values) => values,
+        }
     }
 
     /// Creates new node with inner value
     #[inline]
     pub fn new(value: T) -> Self {
         trace!("new()");
-        let next = FillOnceAtomicOption::default();
-        Self { value, next }
+        Self::from(Values::One(value))
+    }
+
+    /// Creates new node with every inner value, `values` must not be empty
+    #[inline]
+    pub fn from_values(values: Box<[T]>) -> Self {
+        trace!("from_values({})", values.len());
+        debug_assert!(!values.is_empty());
+        Self::from(Values::Many(values))
+    }
+
+    /// Removes value at `offset`, the node must have more than one value (it can't be left empty)
+    #[inline]
+    pub fn remove_value(&mut self, offset: usize) -> T {
+        trace!("remove_value({})", offset);
+        debug_assert!(self.values().len() > 1);
+        let values = match &mut self.values {
+            Values::Many(values) => values,
+            Values::One(_) => unreachable!("node with one value can't be left empty"),
+        };
+        let mut vec = Vec::from(take(values));
+        let value = vec.remove(offset);
+        *values = vec.into_boxed_slice();
+        value
+    }
+
+    /// Keeps only the values that match `f` (which may mutate them), returning how many were kept
+    ///
+    /// The node may be left empty, in that case the caller must unlink it
+    #[inline]
+    pub fn retain_values<F: FnMut(&mut T) -> bool>(&mut self, f: &mut F) -> usize {
+        trace!("retain_values()");
+        match &mut self.values {
+            Values::One(value) => f(value) as usize,
+            Values::Many(values) => {
+                let mut vec = Vec::from(take(values));
+                vec.retain_mut(|value| f(value));
+                *values = vec.into_boxed_slice();
+                values.len()
+            }
+        }
     }
 
     /// Gets next pointer
+    ///
+    /// `Acquire` pairs with the `Release` in `try_store_next`, so the node is fully initialized before it's read
     #[inline]
     pub fn next(&self) -> Option<&Self> {
         trace!("next()");
-        self.next.get_ref(Ordering::Relaxed)
+        self.next.get_ref(Ordering::Acquire)
+    }
+
+    /// Gets raw next pointer, keeping the provenance of the owning `Box` (to mutate nodes with exclusive access)
+    #[inline]
+    pub fn next_ptr(&self) -> Option<NonNull<Self>> {
+        trace!("next_ptr()");
+        NonNull::new(self.next.get_raw(Ordering::Acquire))
+    }
+
+    /// Unlinks next node (and the rest of the chain) from this node, the caller takes its ownership
+    #[inline]
+    pub fn take_next(&mut self) -> Option<NonNull<Self>> {
+        trace!("take_next()");
+        self.next.take_raw(Ordering::Relaxed)
+    }
+
+    /// Extracts inner value, dropping the rest of the chain (if any), the node must have a single value
+    #[inline]
+    pub fn into_value(mut self) -> T {
+        trace!("into_value()");
+        debug_assert_eq!(self.values().len(), 1);
+        // We own the rest of the chain after unlinking it, so we can `Box` it back to drop it
+        drop(
+            self.take_next()
+                .map(|nn| unsafe { Box::from_raw(nn.as_ptr()) }),
+        );
+        // `Drop` only walks `next`, which is now empty, so skipping it leaks nothing
+        let node = ManuallyDrop::new(self);
+        match unsafe { read(&node.values) } {
+            Values::One(value) => value,
+            Values::Many(values) => Vec::from(values)
+                .pop()
+                .expect("node values are never empty"),
+        }
     }
 
     /// Inserts next as if there was None
+    ///
+    /// The pointer is stored as is (instead of going through a `Box`), so other copies of it (like `Inner`'s last node) stay valid
+    ///
+    /// # Safety
+    ///
+    /// You must own `node` (and the chain after it), if it fails you still own it
     #[inline]
-    pub fn try_store_next(&self, node: Box<Self>) -> Result<(), NotEmpty> {
+    pub unsafe fn try_store_next(&self, node: NonNull<Self>) -> Result<(), NotEmpty> {
         trace!("try_store_next({:p})", node);
-        self.next.try_store(node, Ordering::Relaxed)
+        self.next.try_store_raw(node, Ordering::Release)
+    }
+}
+
+impl<T> From<Values<T>> for Node<T> {
+    #[inline]
+    fn from(values: Values<T>) -> Self {
+        let next = FillOnceAtomicOption::default();
+        Self { values, next }
     }
 }
 
 /// Default Drop is recursive and causes a stackoverflow easily
+///
+/// If dropping a value panics the rest of the chain (and this node's values) is still freed, the first panic is resumed afterwards
 impl<T> Drop for Node<T> {
     #[inline]
     fn drop(&mut self) {
         debug!("Drop nodes");
+        let mut panic = None;
         let mut node = self.next.take(Ordering::Relaxed);
         while let Some(mut n) = node {
             node = n.next.take(Ordering::Relaxed);
+            // `n` was unlinked, so dropping it only drops its values (a panic can't reach the rest of the chain)
+            if let Err(err) = catch_unwind(AssertUnwindSafe(move || drop(n))) {
+                let _ = panic.get_or_insert(err);
+            }
+        }
+
+        if let Some(err) = panic {
+            // Our own values would be dropped while unwinding, a second panic there aborts
+            let values = replace(&mut self.values, Values::Many(Box::default()));
+            let _ = catch_unwind(AssertUnwindSafe(move || drop(values)));
+            resume_unwind(err);
         }
     }
 }
@@ -63,8 +191,8 @@ impl<T: Debug> Debug for Node<T> {
     #[inline]
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         f.debug_struct("Node")
-            .field("value", &self.value)
-            .field("next", &self.next.get_ref(Ordering::Relaxed))
+            .field("values", &self.values())
+            .field("next", &self.next())
             .finish()
     }
 }
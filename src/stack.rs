@@ -0,0 +1,448 @@
+//! Lock-free LIFO stack (a Treiber stack)
+//!
+//! Popped nodes are only freed when no other pop is running, so their addresses can't be reused while a pop still reads them (the ABA problem)
+
+use crate::prelude::*;
+use std::fmt::{self, Debug, Formatter};
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use std::{
+    iter::successors, marker::PhantomData, mem::ManuallyDrop, ptr::null_mut, ptr::read,
+    ptr::NonNull,
+};
+
+/// Element of a [`Stack`], its value is moved out by the pop that unlinks it
+///
+/// Concurrent pops may still read `next` after that, so it's only freed when no pop is running
+///
+/// [`Stack`]: ./struct.Stack.html
+struct StackNode<T> {
+    /// Inner value (moved out when popped)
+    value: ManuallyDrop<T>,
+    /// Node below this one, or the next retired node after it's popped
+    next: AtomicPtr<StackNode<T>>,
+}
+
+/// Lock-free LIFO stack (a Treiber stack)
+///
+/// `push` links the new node on top of the current head with a compare-and-swap, `pop` swings the head to the node below it
+///
+/// A pop reads `head.next` from a node that another pop may have already unlinked, if it was freed and its address reused by a new push the compare-and-swap would succeed with a stale `next` (the ABA problem)
+///
+/// So popped nodes are retired and only freed when no pop is running (the last pop to leave frees them), a node is never reused while a pop may still hold its address
+///
+/// If pops never stop overlapping the retired nodes (not their values, those are returned by `pop`) are only freed when they do, or when the stack is dropped
+///
+/// ```rust
+/// # use voluntary_servitude::Stack;
+/// # env_logger::init();
+/// let mut stack = Stack::new();
+/// stack.push(1);
+/// stack.push(2);
+/// stack.push(3);
+/// assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+///
+/// assert_eq!(stack.pop(), Some(3));
+/// assert_eq!(stack.pop(), Some(2));
+/// assert_eq!(stack.pop(), Some(1));
+/// assert_eq!(stack.pop(), None);
+/// ```
+///
+/// It can only be shared between threads if `T: Send`, since any of them can pop (or drop) the values
+///
+/// ```compile_fail
+/// # use voluntary_servitude::Stack;
+/// fn assert_sync<T: Sync>() {}
+/// assert_sync::<Stack<std::sync::MutexGuard<'static, u8>>>();
+/// ```
+pub struct Stack<T> {
+    /// Top of the stack
+    head: AtomicOption<StackNode<T>>,
+    /// Popped nodes waiting for the running pops to finish
+    retired: AtomicOption<StackNode<T>>,
+    /// Pops running, nodes are only freed when it's zero
+    poppers: AtomicUsize,
+    /// Opts out of the auto traits, `AtomicOption` would make it `Sync` for `T: Sync` even if `T: !Send`
+    _marker: PhantomData<*mut StackNode<T>>,
+}
+
+// Values are only moved in and out (`iter` requires exclusive access), like `Mutex<T>`
+unsafe impl<T: Send> Send for Stack<T> {}
+unsafe impl<T: Send> Sync for Stack<T> {}
+
+impl<T> Stack<T> {
+    /// Creates new empty `Stack`
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::Stack;
+    /// # env_logger::init();
+    /// let stack = Stack::<()>::new();
+    /// assert_eq!(stack.pop(), None);
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        trace!("new()");
+        Self::default()
+    }
+
+    /// Pushes value on top of the stack
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::Stack;
+    /// # env_logger::init();
+    /// let stack = Stack::new();
+    /// stack.push(1);
+    /// assert_eq!(stack.pop(), Some(1));
+    /// ```
+    #[inline]
+    pub fn push(&self, value: T) {
+        trace!("push()");
+        let value = ManuallyDrop::new(value);
+        let node = Box::into_raw(Box::new(StackNode {
+            value,
+            next: AtomicPtr::default(),
+        }));
+        let head = unsafe { self.head.atomic_ptr() };
+        let mut current = head.load(Ordering::Relaxed);
+        loop {
+            // `node` isn't published yet, so we still own it and can relink it
+            unsafe { &*node }.next.store(current, Ordering::Relaxed);
+            // Sequentially consistent like the pops, they rely on a single order of head changes and `poppers`
+            match head.compare_exchange_weak(current, node, Ordering::SeqCst, Ordering::Relaxed) {
+                Ok(_) => return,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Pops value from the top of the stack
+    ///
+    /// `None` means the stack was empty
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::Stack;
+    /// # env_logger::init();
+    /// let stack = Stack::new();
+    /// stack.push(1);
+    /// stack.push(2);
+    /// assert_eq!(stack.pop(), Some(2));
+    /// assert_eq!(stack.pop(), Some(1));
+    /// assert_eq!(stack.pop(), None);
+    /// ```
+    #[inline]
+    pub fn pop(&self) -> Option<T> {
+        trace!("pop()");
+        let _ = self.poppers.fetch_add(1, Ordering::SeqCst);
+        let head = unsafe { self.head.atomic_ptr() };
+        // Sequentially consistent with `poppers`, a pop that counts itself after we unlinked a node can't see it on top anymore
+        let mut current = head.load(Ordering::SeqCst);
+        while !current.is_null() {
+            // Nodes aren't freed while we are counted in `poppers`, so we can read `next` even if another pop unlinked it (then the CAS fails)
+            let next = unsafe { (*current).next.load(Ordering::Relaxed) };
+            match head.compare_exchange_weak(current, next, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => break,
+                Err(actual) => current = actual,
+            }
+        }
+
+        if current.is_null() {
+            let _ = self.poppers.fetch_sub(1, Ordering::SeqCst);
+            return None;
+        }
+
+        // We unlinked it, so we own its value (other pops only read `next`)
+        let value = unsafe { read(&(*current).value) };
+        self.release(current);
+        Some(ManuallyDrop::into_inner(value))
+    }
+
+    /// Leaves the pop that unlinked `node`, freeing it (and the retired nodes) if no other pop is running, otherwise retiring it
+    fn release(&self, node: *mut StackNode<T>) {
+        if self.poppers.load(Ordering::SeqCst) > 1 {
+            self.retire(node, node);
+            let _ = self.poppers.fetch_sub(1, Ordering::SeqCst);
+            return;
+        }
+
+        // No other pop can reach `node` (it was unlinked before we were alone), nor the nodes retired until now
+        let retired = unsafe { self.retired.atomic_ptr() }.swap(null_mut(), Ordering::SeqCst);
+        if self.poppers.fetch_sub(1, Ordering::SeqCst) == 1 {
+            unsafe { free_chain(retired) };
+        } else if !retired.is_null() {
+            // A pop started after we took them, it may have read a node retired meanwhile
+            let mut last = retired;
+            while let Some(next) = NonNull::new(unsafe { (*last).next.load(Ordering::Relaxed) }) {
+                last = next.as_ptr();
+            }
+            self.retire(retired, last);
+        }
+        drop(unsafe { Box::from_raw(node) });
+    }
+
+    /// Links a chain of popped nodes (`first` to `last`) to the retired nodes
+    fn retire(&self, first: *mut StackNode<T>, last: *mut StackNode<T>) {
+        let retired = unsafe { self.retired.atomic_ptr() };
+        let mut current = retired.load(Ordering::Relaxed);
+        loop {
+            // Pops that still read `last.next` will fail their CAS, since `last` was unlinked
+            unsafe { &*last }.next.store(current, Ordering::Relaxed);
+            match retired.compare_exchange_weak(
+                current,
+                first,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Iterates over the values, from top to bottom
+    ///
+    /// Requires exclusive access, since a concurrent `pop` would move the values out
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::Stack;
+    /// # env_logger::init();
+    /// let mut stack = Stack::new();
+    /// stack.push(1);
+    /// stack.push(2);
+    /// assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&2, &1]);
+    /// assert_eq!(stack.pop(), Some(2));
+    /// assert_eq!(stack.iter().collect::<Vec<_>>(), vec![&1]);
+    /// ```
+    #[inline]
+    pub fn iter(&mut self) -> impl Iterator<Item = &T> {
+        debug!("iter()");
+        let head = unsafe { self.head.get_raw(Ordering::Relaxed).as_ref() };
+        successors(head, |node| unsafe {
+            node.next.load(Ordering::Relaxed).as_ref()
+        })
+        .map(|node| &*node.value)
+    }
+}
+
+/// Frees a chain of popped nodes (their values were already moved out)
+///
+/// # Safety
+///
+/// No pop may be able to read them
+unsafe fn free_chain<T>(mut node: *mut StackNode<T>) {
+    while !node.is_null() {
+        let boxed = Box::from_raw(node);
+        node = boxed.next.load(Ordering::Relaxed);
+    }
+}
+
+impl<T> Default for Stack<T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            head: AtomicOption::default(),
+            retired: AtomicOption::default(),
+            poppers: AtomicUsize::new(0),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Default Drop would only free the top node
+///
+/// If dropping a value panics the rest of the stack is still freed, the first panic is resumed afterwards
+impl<T> Drop for Stack<T> {
+    #[inline]
+    fn drop(&mut self) {
+        debug!("Drop Stack");
+        let mut panic = None;
+        let mut node = self.head.take(Ordering::Relaxed);
+        while let Some(mut n) = node {
+            let next = n.next.load(Ordering::Relaxed);
+            node = NonNull::new(next).map(|next| unsafe { Box::from_raw(next.as_ptr()) });
+            let dropped = catch_unwind(AssertUnwindSafe(|| unsafe {
+                ManuallyDrop::drop(&mut n.value)
+            }));
+            if let Err(err) = dropped {
+                let _ = panic.get_or_insert(err);
+            }
+        }
+
+        let retired = self.retired.take(Ordering::Relaxed).map(Box::into_raw);
+        unsafe { free_chain(retired.unwrap_or_else(null_mut)) };
+
+        if let Some(err) = panic {
+            resume_unwind(err);
+        }
+    }
+}
+
+impl<T> Debug for Stack<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Stack")
+            .field(&self.head.get_raw(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stack;
+    use crate::setup_logger;
+    use std::sync::{atomic::AtomicUsize, atomic::Ordering, Arc};
+    use std::thread::spawn;
+
+    #[test]
+    fn lifo() {
+        setup_logger();
+        let mut stack = Stack::new();
+        for i in 0..1000 {
+            stack.push(i);
+        }
+        assert_eq!(
+            stack.iter().copied().collect::<Vec<_>>(),
+            (0..1000).rev().collect::<Vec<_>>()
+        );
+        for i in (0..1000).rev() {
+            assert_eq!(stack.pop(), Some(i));
+        }
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.iter().count(), 0);
+    }
+
+    #[test]
+    fn drop_frees_values() {
+        setup_logger();
+        let value = Arc::new(());
+        let stack = Stack::new();
+        for _ in 0..100_000 {
+            stack.push(Arc::clone(&value));
+        }
+        drop(stack.pop());
+        assert_eq!(Arc::strong_count(&value), 100_000);
+        drop(stack);
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn drop_panic_frees_stack() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        setup_logger();
+
+        struct Bomb<'a>(usize, &'a AtomicUsize);
+        impl Drop for Bomb<'_> {
+            fn drop(&mut self) {
+                let _ = self.1.fetch_add(1, Ordering::Relaxed);
+                if self.0 % 3 == 1 {
+                    panic!("bomb {}", self.0);
+                }
+            }
+        }
+
+        let drops = AtomicUsize::new(0);
+        let stack = Stack::new();
+        for n in 0..10 {
+            stack.push(Bomb(n, &drops));
+        }
+        let err = catch_unwind(AssertUnwindSafe(move || drop(stack))).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<String>().map(String::as_str),
+            Some("bomb 7")
+        );
+        assert_eq!(drops.load(Ordering::Relaxed), 10);
+    }
+
+    #[test]
+    fn concurrent_pops_drain() {
+        setup_logger();
+        const THREADS: usize = 4;
+        const ELEMENTS: usize = 10_000;
+        let stack = Arc::new(Stack::new());
+        for i in 0..THREADS * ELEMENTS {
+            stack.push(i);
+        }
+
+        // `pop` only returns `None` when the stack is empty
+        let poppers: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let stack = Arc::clone(&stack);
+                spawn(move || {
+                    let mut values = vec![];
+                    while let Some(value) = stack.pop() {
+                        values.push(value);
+                    }
+                    values
+                })
+            })
+            .collect();
+
+        let mut values: Vec<_> = poppers
+            .into_iter()
+            .flat_map(|popper| popper.join().expect("Failed to join thread"))
+            .collect();
+        values.sort_unstable();
+        assert_eq!(values, (0..THREADS * ELEMENTS).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn concurrent_push_pop() {
+        setup_logger();
+        const THREADS: usize = 4;
+        const ELEMENTS: usize = 10_000;
+        let stack = Arc::new(Stack::new());
+        let popped = Arc::new(AtomicUsize::new(0));
+
+        let pushers: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let stack = Arc::clone(&stack);
+                spawn(move || {
+                    for i in 0..ELEMENTS {
+                        stack.push(t * ELEMENTS + i);
+                    }
+                })
+            })
+            .collect();
+
+        let poppers: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let (stack, popped) = (Arc::clone(&stack), Arc::clone(&popped));
+                spawn(move || {
+                    let mut values = vec![];
+                    // The stack may be empty while pushers are still running
+                    while popped.load(Ordering::Relaxed) < THREADS * ELEMENTS {
+                        if let Some(value) = stack.pop() {
+                            let _ = popped.fetch_add(1, Ordering::Relaxed);
+                            values.push(value);
+                        }
+                    }
+                    values
+                })
+            })
+            .collect();
+
+        for pusher in pushers {
+            pusher.join().expect("Failed to join thread");
+        }
+        let mut values: Vec<_> = poppers
+            .into_iter()
+            .flat_map(|popper| popper.join().expect("Failed to join thread"))
+            .collect();
+
+        // No element was lost nor duplicated
+        values.sort_unstable();
+        assert_eq!(values, (0..THREADS * ELEMENTS).collect::<Vec<_>>());
+        assert_eq!(stack.pop(), None);
+    }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Stack<()>>();
+    }
+
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<Stack<()>>();
+    }
+}
@@ -5,3 +5,6 @@ mod rayon;
 
 #[cfg(feature = "serde-traits")]
 mod serde;
+
+#[cfg(feature = "serde-traits")]
+pub use self::serde::{bytes as serde_bytes, deserialize_bounded};
@@ -24,6 +24,85 @@ impl<T: Send + Sync> VoluntaryServitude<T> {
         trace!("par_extend()");
         par_iter.into_par_iter().for_each(|el| self.append(el));
     }
+
+    /// Calls `f` on every element of the current snapshot in parallel
+    ///
+    /// Elements appended after it's called aren't visited
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// let list = vs![1, 2, 3];
+    /// let sum = AtomicUsize::new(0);
+    /// list.par_for_each(|&v| {
+    ///     sum.fetch_add(v, Ordering::Relaxed);
+    /// });
+    /// assert_eq!(sum.into_inner(), 6);
+    /// ```
+    #[cfg_attr(docs_rs_workaround, doc(cfg(feature = "rayon-traits")))]
+    #[inline]
+    pub fn par_for_each<F>(&self, f: F)
+    where
+        F: Fn(&T) + Sync,
+    {
+        trace!("par_for_each()");
+        let frozen = self.freeze();
+        (0..frozen.len())
+            .into_par_iter()
+            .for_each(|index| f(&frozen[index]));
+    }
+}
+
+impl<T: Send + Sync> Frozen<T> {
+    /// Makes parallel iterator over snapshot's elements
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// use rayon::prelude::*;
+    /// let list = vs![1, 2, 3];
+    /// assert_eq!(list.freeze().par_iter().sum::<i32>(), 6);
+    /// ```
+    #[cfg_attr(docs_rs_workaround, doc(cfg(feature = "rayon-traits")))]
+    #[inline]
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = &T> {
+        trace!("par_iter()");
+        (0..self.len())
+            .into_par_iter()
+            .map(move |index| &self[index])
+    }
+
+    /// Makes parallel iterator over chunks of `chunk` elements (the last one may be shorter), each chunk is iterated sequentially
+    ///
+    /// Coarse-grained tasks reduce rayon's overhead when the work per element is cheap
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk` is `0`
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// use rayon::prelude::*;
+    /// let list = vs![1, 2, 3, 4, 5];
+    /// let sums: Vec<i32> = list.freeze().par_iter_chunked(2).map(Iterator::sum).collect();
+    /// assert_eq!(sums, vec![3, 7, 5]);
+    /// ```
+    #[cfg_attr(docs_rs_workaround, doc(cfg(feature = "rayon-traits")))]
+    #[inline]
+    pub fn par_iter_chunked(
+        &self,
+        chunk: usize,
+    ) -> impl IndexedParallelIterator<Item = impl Iterator<Item = &T>> {
+        trace!("par_iter_chunked({})", chunk);
+        assert!(chunk > 0, "par_iter_chunked chunk must be greater than 0");
+        let len = self.len();
+        (0..len.div_ceil(chunk)).into_par_iter().map(move |index| {
+            let start = index * chunk;
+            (start..len.min(start + chunk)).map(move |index| &self[index])
+        })
+    }
 }
 
 #[cfg_attr(docs_rs_workaround, doc(cfg(feature = "rayon-traits")))]
@@ -60,6 +139,46 @@ mod tests {
         assert_eq!(vs.iter().sum::<u8>(), sum * 2);
     }
 
+    #[test]
+    fn par_for_each() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        setup_logger();
+        let vs: VS<usize> = (1..100).collect();
+        let calls = AtomicUsize::new(0);
+        let sum = AtomicUsize::new(0);
+        vs.par_for_each(|&v| {
+            let _ = calls.fetch_add(1, Ordering::Relaxed);
+            let _ = sum.fetch_add(v, Ordering::Relaxed);
+        });
+        assert_eq!(calls.into_inner(), 99);
+        assert_eq!(sum.into_inner(), (1..100).sum::<usize>());
+    }
+
+    #[test]
+    fn frozen_par_iter_chunked() {
+        setup_logger();
+        let vs: VS<usize> = (0..1000).collect();
+        let frozen = vs.freeze();
+        let sum = vs.iter().sum::<usize>();
+        assert_eq!(frozen.par_iter().sum::<usize>(), sum);
+        for &chunk in &[1, 2, 7, 100, 999, 1000, 5000] {
+            let chunks = frozen.par_iter_chunked(chunk);
+            assert_eq!(chunks.len(), 1000usize.div_ceil(chunk));
+            let sum_chunked = chunks.map(Iterator::sum::<usize>).sum::<usize>();
+            assert_eq!(sum_chunked, sum);
+        }
+
+        let flattened: Vec<_> = frozen.par_iter_chunked(3).flat_map_iter(|c| c).collect();
+        assert!(flattened.into_iter().eq(frozen.iter()));
+        assert_eq!(VS::<u8>::new().freeze().par_iter_chunked(4).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "par_iter_chunked chunk must be greater than 0")]
+    fn frozen_par_iter_chunked_zero() {
+        let _ = vs![1].freeze().par_iter_chunked(0);
+    }
+
     #[test]
     fn from_par_iter() {
         setup_logger();
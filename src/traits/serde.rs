@@ -1,6 +1,7 @@
-//! Serde's `Serialize`/`Deserialize` trait implementations for [`VoluntaryServitude`]
+//! Serde's `Serialize`/`Deserialize` trait implementations for [`VoluntaryServitude`] and [`Iter`]
 //!
 //! [`VoluntaryServitude`]: ../struct.VoluntaryServitude.html#implementations
+//! [`Iter`]: ../struct.Iter.html#implementations
 //!
 //! Enable the feature:
 //!
@@ -11,13 +12,14 @@
 //! voluntary_servitude = { version = "4", features = "serde-traits" }
 //! ```
 
-use crate::{prelude::*, voluntary_servitude::Inner};
-use serde::{de::SeqAccess, de::Visitor, ser::SerializeSeq};
+use crate::{atomics::FillOnceAtomicArc, prelude::*, voluntary_servitude::Inner};
+use serde::{de::Error, de::SeqAccess, de::Unexpected, de::Visitor, ser::SerializeSeq};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::{fmt, fmt::Formatter, marker::PhantomData};
+use std::{fmt, fmt::Formatter, iter::from_fn, iter::FromIterator, marker::PhantomData};
+use std::{sync::atomic::Ordering, sync::Arc};
 
-/// Abstracts deserializer visitor
-struct InnerVisitor<'a, 'b, T: 'b + Deserialize<'a>>(pub PhantomData<(&'a (), &'b T)>);
+/// Abstracts deserializer visitor, erroring out once there are more than `max` elements
+struct InnerVisitor<'a, 'b, T: 'b + Deserialize<'a>>(pub usize, pub PhantomData<(&'a (), &'b T)>);
 
 impl<'a, 'b, T: 'b + Deserialize<'a>> Visitor<'a> for InnerVisitor<'a, 'b, T> {
     type Value = Inner<T>;
@@ -27,13 +29,32 @@ impl<'a, 'b, T: 'b + Deserialize<'a>> Visitor<'a> for InnerVisitor<'a, 'b, T> {
         write!(f, "a list")
     }
 
+    /// Builds the chain in bulk with `FromIterator` (a node chain can't reserve capacity, so `size_hint` is only logged)
     #[inline]
     fn visit_seq<A: SeqAccess<'a>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
-        let inner: Inner<T> = Inner::default();
-        while let Some(value) = seq.next_element()? {
-            inner.append(value);
+        debug!("visit_seq({:?}) with max {}", seq.size_hint(), self.0);
+        let max = self.0;
+        let too_long = |len| A::Error::invalid_length(len, &&*format!("at most {} elements", max));
+        if let Some(len) = seq.size_hint().filter(|&len| len > max) {
+            return Err(too_long(len));
         }
-        Ok(inner)
+
+        let (mut error, mut len) = (None, 0);
+        let inner = Inner::from_iter(from_fn(|| match seq.next_element() {
+            Ok(Some(_)) if len == max => {
+                error = Some(too_long(len + 1));
+                None
+            }
+            Ok(value) => {
+                len += 1;
+                value
+            }
+            Err(err) => {
+                error = Some(err);
+                None
+            }
+        }));
+        error.map_or(Ok(inner), Err)
     }
 }
 
@@ -41,21 +62,44 @@ impl<'a, T: Deserialize<'a>> Deserialize<'a> for Inner<T> {
     #[inline]
     fn deserialize<D: Deserializer<'a>>(des: D) -> Result<Self, D::Error> {
         debug!("Deserialize Inner");
-        des.deserialize_seq(InnerVisitor(PhantomData))
+        des.deserialize_seq(InnerVisitor(usize::MAX, PhantomData))
     }
 }
 
+/// Deserializes a [`VS`] erroring out once more than `max` elements are found (to protect against huge untrusted input)
+///
+/// [`VS`]: ../type.VS.html
+///
+/// ```rust
+/// # use voluntary_servitude::{deserialize_bounded, vs, VS};
+/// # env_logger::init();
+/// let mut des = serde_json::Deserializer::from_str("[1, 2, 3]");
+/// let list: VS<u8> = deserialize_bounded(&mut des, 3).unwrap();
+/// assert_eq!(list, vs![1, 2, 3]);
+///
+/// let mut des = serde_json::Deserializer::from_str("[1, 2, 3]");
+/// assert!(deserialize_bounded::<u8, _>(&mut des, 2).is_err());
+/// ```
+#[cfg_attr(docs_rs_workaround, doc(cfg(feature = "serde-traits")))]
+#[inline]
+pub fn deserialize_bounded<'a, T, D>(des: D, max: usize) -> Result<VoluntaryServitude<T>, D::Error>
+where
+    T: Deserialize<'a>,
+    D: Deserializer<'a>,
+{
+    debug!("deserialize_bounded({})", max);
+    let inner = des.deserialize_seq(InnerVisitor(max, PhantomData))?;
+    Ok(VoluntaryServitude::from(inner))
+}
+
 #[cfg_attr(docs_rs_workaround, doc(cfg(feature = "serde-traits")))]
 impl<T: Serialize> Serialize for VoluntaryServitude<T> {
     #[inline]
     fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
         trace!("Serialize VoluntaryServitude");
-        let len = self.len();
-        let mut sequence = ser.serialize_seq(Some(len))?;
-        for (el, _) in self.iter().zip(0..len) {
-            sequence.serialize_element(el)?;
-        }
-        sequence.end()
+        // Elements must come from the same snapshot, a `clear` in between would mix two chains
+        let mut iter = Iter::from(self.snapshot_arc());
+        serialize_linked(&mut iter, ser)
     }
 }
 
@@ -67,14 +111,212 @@ impl<'a, T: Deserialize<'a>> Deserialize<'a> for VoluntaryServitude<T> {
     }
 }
 
+/// Serializes elements referenced by [`Iter`] from the start of the chain (ignoring its index)
+///
+/// [`Iter`]: ../struct.Iter.html
+struct IterElements<'a, T>(&'a Iter<T>);
+
+impl<'a, T: Serialize> Serialize for IterElements<'a, T> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        let mut iter = Iter::from(Arc::clone(self.0.inner()));
+        serialize_linked(&mut iter, ser)
+    }
+}
+
+/// Serializes the elements `iter` reaches, declaring exactly their count
+///
+/// The snapshot's length may count a node that isn't linked yet (its appender was preempted between swapping the last node and linking it), so the elements are collected before the length is declared
+#[inline]
+fn serialize_linked<T: Serialize, S: Serializer>(
+    iter: &mut Iter<T>,
+    ser: S,
+) -> Result<S::Ok, S::Error> {
+    let len = iter.len();
+    let elements: Vec<&T> = iter.take(len).collect();
+    let mut sequence = ser.serialize_seq(Some(elements.len()))?;
+    for el in elements {
+        sequence.serialize_element(el)?;
+    }
+    sequence.end()
+}
+
+/// Serializes `Iter` as a `(index, elements)` tuple, so consumption can be resumed after deserializing
+#[cfg_attr(docs_rs_workaround, doc(cfg(feature = "serde-traits")))]
+impl<T: Serialize> Serialize for Iter<T> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        trace!("Serialize Iter");
+        (self.index(), IterElements(self)).serialize(ser)
+    }
+}
+
+/// Deserializes `Iter` from a `(index, elements)` tuple, positioned at `index`
+#[cfg_attr(docs_rs_workaround, doc(cfg(feature = "serde-traits")))]
+impl<'a, T: Deserialize<'a>> Deserialize<'a> for Iter<T> {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> Result<Self, D::Error> {
+        debug!("Deserialize Iter");
+        let (index, inner) = <(usize, Inner<T>)>::deserialize(des)?;
+        if index > inner.len() {
+            let unexpected = Unexpected::Unsigned(index as u64);
+            return Err(D::Error::invalid_value(
+                unexpected,
+                &"index inside elements",
+            ));
+        }
+        Ok(Iter::iter_from(Arc::new(inner), index))
+    }
+}
+
+/// Serializes `FillOnceAtomicArc` as `Option<T>` (the `Arc` is transparent, so shared values serialize like owned ones)
+#[cfg_attr(docs_rs_workaround, doc(cfg(feature = "serde-traits")))]
+impl<T: Serialize> Serialize for FillOnceAtomicArc<T> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        trace!("Serialize FillOnceAtomicArc");
+        self.get_ref(Ordering::Acquire).serialize(ser)
+    }
+}
+
+/// Deserializes `FillOnceAtomicArc` from `Option<T>`, filled with a new `Arc` or empty
+#[cfg_attr(docs_rs_workaround, doc(cfg(feature = "serde-traits")))]
+impl<'a, T: Deserialize<'a>> Deserialize<'a> for FillOnceAtomicArc<T> {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> Result<Self, D::Error> {
+        debug!("Deserialize FillOnceAtomicArc");
+        Option::<T>::deserialize(des).map(|value| Self::from(value.map(Arc::new)))
+    }
+}
+
+/// Serializes `VS<u8>` as bytes, meant to be used with `#[serde(with = "voluntary_servitude::serde_bytes")]`
+///
+/// Human readable formats (like JSON) get a lowercase hex string, compact ones (like bincode) get a byte array
+///
+/// ```rust
+/// # use voluntary_servitude::{vs, VS};
+/// # env_logger::init();
+/// use serde_derive::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Payload(#[serde(with = "voluntary_servitude::serde_bytes")] VS<u8>);
+///
+/// let json = serde_json::to_string(&Payload(vs![0, 15, 255])).unwrap();
+/// assert_eq!(json, "\"000fff\"");
+/// let payload: Payload = serde_json::from_str(&json).unwrap();
+/// assert_eq!(payload.0, vs![0, 15, 255]);
+/// ```
+#[cfg_attr(docs_rs_workaround, doc(cfg(feature = "serde-traits")))]
+pub mod bytes {
+    use crate::{prelude::*, voluntary_servitude::Inner};
+    use serde::{de::Error, de::Unexpected, de::Visitor, Deserializer, Serializer};
+    use std::fmt::{self, Formatter, Write};
+
+    /// Serializes current snapshot as a hex string or as bytes, depending on `is_human_readable`
+    #[inline]
+    pub fn serialize<S: Serializer>(vs: &VS<u8>, ser: S) -> Result<S::Ok, S::Error> {
+        trace!("serialize bytes");
+        let bytes = vs.to_boxed_slice();
+        if !ser.is_human_readable() {
+            return ser.serialize_bytes(&bytes);
+        }
+
+        let mut hex = String::with_capacity(bytes.len() * 2);
+        for byte in bytes.iter() {
+            let _ = write!(hex, "{:02x}", byte);
+        }
+        ser.serialize_str(&hex)
+    }
+
+    /// Deserializes a hex string or bytes, depending on `is_human_readable`
+    #[inline]
+    pub fn deserialize<'a, D: Deserializer<'a>>(des: D) -> Result<VS<u8>, D::Error> {
+        trace!("deserialize bytes");
+        if des.is_human_readable() {
+            des.deserialize_str(BytesVisitor)
+        } else {
+            des.deserialize_byte_buf(BytesVisitor)
+        }
+    }
+
+    /// Builds `VS` from a hex string or bytes, stored in a single node
+    struct BytesVisitor;
+
+    impl<'a> Visitor<'a> for BytesVisitor {
+        type Value = VS<u8>;
+
+        #[inline]
+        fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+            write!(f, "a hex string or bytes")
+        }
+
+        #[inline]
+        fn visit_str<E: Error>(self, hex: &str) -> Result<Self::Value, E> {
+            let invalid = || E::invalid_value(Unexpected::Str(hex), &"an even length hex string");
+            if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+                return Err(invalid());
+            }
+            // Each digit is decoded by itself, `u8::from_str_radix` would accept a sign (like "+f")
+            let digit = |byte: u8| char::from(byte).to_digit(16);
+            let bytes = hex
+                .as_bytes()
+                .chunks(2)
+                .map(|pair| Some((digit(pair[0])? << 4 | digit(pair[1])?) as u8))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(invalid)?;
+            self.visit_byte_buf(bytes)
+        }
+
+        #[inline]
+        fn visit_bytes<E: Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+            self.visit_byte_buf(bytes.to_vec())
+        }
+
+        #[inline]
+        fn visit_byte_buf<E: Error>(self, bytes: Vec<u8>) -> Result<Self::Value, E> {
+            let inner = Inner::default();
+            let _ = inner.append_values(bytes.into_boxed_slice());
+            Ok(VoluntaryServitude::from(inner))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::VS;
+    use crate::{Iter, VS};
     use serde::{Deserialize, Serialize};
 
     #[derive(Serialize, Deserialize)]
     struct Derive<T>(pub VS<T>);
 
+    #[test]
+    fn fill_once_atomic_arc() {
+        use crate::atomics::FillOnceAtomicArc;
+        use std::sync::{atomic::Ordering, Arc};
+
+        let filled = FillOnceAtomicArc::<Vec<i32>>::from(vec![1, 2]);
+        let string = serde_json::to_string(&filled).unwrap();
+        assert_eq!(string, "[1,2]");
+        let filled: FillOnceAtomicArc<Vec<i32>> = serde_json::from_str(&string).unwrap();
+        assert_eq!(filled.get_ref(Ordering::Relaxed), Some(&vec![1, 2]));
+
+        let empty = FillOnceAtomicArc::<Vec<i32>>::default();
+        let string = serde_json::to_string(&empty).unwrap();
+        assert_eq!(string, "null");
+        let empty: FillOnceAtomicArc<Vec<i32>> = serde_json::from_str(&string).unwrap();
+        assert_eq!(empty.get_ref(Ordering::Relaxed), None);
+
+        // Arcs sharing a value serialize just like the value
+        let shared = Arc::new(5);
+        let first = FillOnceAtomicArc::<i32>::from(Arc::clone(&shared));
+        let second = FillOnceAtomicArc::<i32>::from(shared);
+        assert_eq!(
+            serde_json::to_string(&first).unwrap(),
+            serde_json::to_string(&second).unwrap()
+        );
+        assert_eq!(serde_json::to_string(&first).unwrap(), "5");
+    }
+
     #[test]
     fn derive_json() {
         let string = serde_json::to_string(&Derive(vs![1u8, 2u8, 3u8, 4u8])).unwrap();
@@ -91,4 +333,185 @@ mod tests {
         let vs: VS<u8> = serde_json::from_str(&string).unwrap();
         assert_eq!(vs.iter().collect::<Vec<_>>(), vec![&1u8, &2u8, &3u8, &4u8]);
     }
+
+    #[test]
+    fn large_json() {
+        let vec: Vec<u32> = (0..100_000).collect();
+        let string = serde_json::to_string(&vec).unwrap();
+        let vs: VS<u32> = serde_json::from_str(&string).unwrap();
+        assert_eq!(vs.len(), vec.len());
+        assert_eq!(vs.last(), Some(99_999));
+        assert!(vs.iter().eq(vec.iter()));
+
+        assert!(serde_json::from_str::<VS<u32>>("[1, 2, \"3\"]").is_err());
+    }
+
+    #[test]
+    fn concurrent_json() {
+        use std::{sync::Arc, thread::spawn};
+        let vs = Arc::new(VS::default());
+        let writer = {
+            let vs = Arc::clone(&vs);
+            spawn(move || {
+                for i in 0..20_000u32 {
+                    vs.append(i);
+                    if i % 5_000 == 4_999 {
+                        vs.clear();
+                    }
+                }
+            })
+        };
+
+        while !writer.is_finished() {
+            let string = serde_json::to_string(&*vs).unwrap();
+            let elements: Vec<u32> = serde_json::from_str(&string).unwrap();
+            assert!(elements
+                .iter()
+                .zip(elements.iter().skip(1))
+                .all(|(a, b)| a + 1 == *b));
+        }
+        writer.join().unwrap();
+        assert_eq!(serde_json::to_string(&*vs).unwrap(), "[]");
+    }
+
+    #[test]
+    fn concurrent_bincode() {
+        use std::{sync::Arc, thread::spawn};
+        const WRITERS: u32 = 4;
+        let vs = Arc::new(VS::default());
+        let writers: Vec<_> = (0..WRITERS)
+            .map(|w| {
+                let vs = Arc::clone(&vs);
+                spawn(move || {
+                    for i in 0..5_000u32 {
+                        vs.append(w + i * WRITERS);
+                    }
+                })
+            })
+            .collect();
+
+        // A declared length that doesn't match the elements serialized corrupts bincode's stream
+        while writers.iter().any(|writer| !writer.is_finished()) {
+            let bytes = bincode::serialize(&*vs).unwrap();
+            let mut elements: Vec<u32> = bincode::deserialize(&bytes).unwrap();
+            elements.sort_unstable();
+            elements.dedup();
+            assert_eq!(bytes.len(), 8 + elements.len() * 4);
+            let iter: Iter<u32> =
+                bincode::deserialize(&bincode::serialize(&vs.iter()).unwrap()).unwrap();
+            assert!(iter.len() <= vs.len());
+        }
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        let elements: Vec<u32> = bincode::deserialize(&bincode::serialize(&*vs).unwrap()).unwrap();
+        assert_eq!(elements.len(), (WRITERS * 5_000) as usize);
+    }
+
+    #[test]
+    fn bincode_unlinked_node() {
+        use crate::node::Node;
+        use std::ptr::NonNull;
+        let vs = vs![1u32, 2];
+        let inner = vs.snapshot_arc();
+
+        // An appender swapped the last node but was preempted before linking it, another one linked after it and increased the size
+        let preempted = Box::into_raw(Box::new(Node::new(3u32)));
+        let previous = inner.swap_last(preempted).unwrap();
+        let next = Box::into_raw(Box::new(Node::new(4u32)));
+        assert_eq!(unsafe { inner.append_chain(next, next, 1) }, 3);
+
+        let bytes = bincode::serialize(&vs).unwrap();
+        assert_eq!(
+            bincode::deserialize::<Vec<u32>>(&bytes).unwrap(),
+            vec![1, 2]
+        );
+        let bytes = bincode::serialize(&vs.iter()).unwrap();
+        let mut iter: Iter<u32> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&1, &2]);
+
+        // The preempted appender links its node
+        let linked = unsafe {
+            previous
+                .as_ref()
+                .try_store_next(NonNull::new(preempted).unwrap())
+        };
+        assert!(linked.is_ok());
+        let bytes = bincode::serialize(&vs).unwrap();
+        assert_eq!(
+            bincode::deserialize::<Vec<u32>>(&bytes).unwrap(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn bounded_json() {
+        use super::deserialize_bounded;
+        use serde_json::Deserializer;
+        let bounded =
+            |json, max| deserialize_bounded::<u8, _>(&mut Deserializer::from_str(json), max);
+        assert_eq!(bounded("[1, 2, 3]", 3).unwrap(), vs![1, 2, 3]);
+        assert_eq!(bounded("[]", 0).unwrap(), vs![]);
+        assert!(bounded("[1, 2, 3]", 2).is_err());
+        assert!(bounded("[1]", 0).is_err());
+        assert!(bounded("[1, \"2\"]", 5).is_err());
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Bytes(#[serde(with = "super::bytes")] VS<u8>);
+
+    #[test]
+    fn bytes_json() {
+        let vs: VS<u8> = (0..=255).collect();
+        let string = serde_json::to_string(&Bytes(vs.clone())).unwrap();
+        assert_eq!(string.len(), 2 + 256 * 2);
+        assert!(string.starts_with("\"000102"));
+        let bytes: Bytes = serde_json::from_str(&string).unwrap();
+        assert_eq!(bytes.0, vs);
+
+        let empty: Bytes = serde_json::from_str("\"\"").unwrap();
+        assert!(empty.0.is_empty());
+        assert_eq!(serde_json::to_string(&empty).unwrap(), "\"\"");
+        assert!(serde_json::from_str::<Bytes>("\"abc\"").is_err());
+        assert!(serde_json::from_str::<Bytes>("\"zz\"").is_err());
+        assert!(serde_json::from_str::<Bytes>("\"+f+f\"").is_err());
+        assert!(serde_json::from_str::<Bytes>("\"-1\"").is_err());
+        let mixed: Bytes = serde_json::from_str("\"aBcD\"").unwrap();
+        assert_eq!(mixed.0, vs![0xab, 0xcd]);
+        assert!(serde_json::from_str::<Bytes>("\"é1\"").is_err());
+        assert!(serde_json::from_str::<Bytes>("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn bytes_bincode() {
+        let vs = vs![0u8, 1, 2, 255];
+        let encoded = bincode::serialize(&Bytes(vs.clone())).unwrap();
+        // Length prefix followed by the raw bytes
+        assert_eq!(encoded.len(), 8 + 4);
+        assert_eq!(&encoded[8..], &[0, 1, 2, 255]);
+        let bytes: Bytes = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(bytes.0, vs);
+        assert_eq!(bytes.0.verify_integrity(), Ok(()));
+    }
+
+    #[test]
+    fn iter_resume_json() {
+        let vs = vs![1u8, 2u8, 3u8, 4u8];
+        let mut iter = vs.iter();
+        assert_eq!((&mut iter).next(), Some(&1));
+        assert_eq!((&mut iter).next(), Some(&2));
+
+        let string = serde_json::to_string(&iter).unwrap();
+        let mut resumed: Iter<u8> = serde_json::from_str(&string).unwrap();
+        assert_eq!(resumed.index(), 2);
+        assert_eq!(resumed.len(), 4);
+        assert_eq!((&mut resumed).collect::<Vec<_>>(), vec![&3u8, &4u8]);
+    }
+
+    #[test]
+    fn iter_invalid_index_json() {
+        assert!(serde_json::from_str::<Iter<u8>>("[3,[1,2]]").is_err());
+        let consumed: Iter<u8> = serde_json::from_str("[2,[1,2]]").unwrap();
+        assert!(consumed.is_empty());
+    }
 }
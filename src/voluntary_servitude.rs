@@ -2,13 +2,43 @@
 
 use crate::{node::Node, prelude::*};
 use parking_lot::RwLock;
+use std::cmp::{min, Ordering as CmpOrdering};
+use std::collections::HashSet;
 use std::fmt::{self, Debug, Formatter};
+use std::hash::Hash;
+use std::io::{self, Write};
 use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
-use std::{iter::Extend, iter::FromIterator, mem::swap, ptr::null_mut, ptr::NonNull, sync::Arc};
+use std::sync::{Arc, Weak};
+use std::{
+    iter::from_fn, iter::successors, iter::Extend, iter::FromIterator, mem::replace, mem::swap,
+    ptr::null_mut, ptr::NonNull,
+};
 
 /// Holds actual [`VoluntaryServitude`]'s data, abstracts safety
 ///
+/// Obtained with [`snapshot_arc`], it can be converted into an [`Iter`] with `Iter::from`
+///
 /// [`VoluntaryServitude`]: ./struct.VoluntaryServitude.html
+/// [`snapshot_arc`]: ./struct.VoluntaryServitude.html#method.snapshot_arc
+/// [`Iter`]: ./struct.Iter.html
+///
+/// ```rust
+/// # use voluntary_servitude::vs;
+/// # env_logger::init();
+/// use voluntary_servitude::Iter;
+/// let list = vs![1, 2];
+/// let snapshot = list.snapshot_arc();
+/// assert_eq!(snapshot.len(), 2);
+/// assert_eq!(Iter::from(snapshot).collect::<Vec<_>>(), vec![&1, &2]);
+/// ```
+///
+/// It's read-only outside of this crate, appends must go through [`VoluntaryServitude`] (its lock and generation track them)
+///
+/// ```compile_fail
+/// # use voluntary_servitude::vs;
+/// let list = vs![1, 2];
+/// list.snapshot_arc().append(3);
+/// ```
 #[derive(Debug)]
 pub struct Inner<T> {
     /// Number of elements inside `Inner`
@@ -33,17 +63,19 @@ impl<T> Default for Inner<T> {
 
 impl<T> Inner<T> {
     /// Atomically extracts pointer to first node
+    ///
+    /// Nodes are published with `Release` and loaded with `Acquire`, so they can be derefed by other threads
     #[inline]
-    pub fn first_node(&self) -> Option<NonNull<Node<T>>> {
-        let nn = NonNull::new(self.first_node.get_raw(Ordering::Relaxed));
+    pub(crate) fn first_node(&self) -> Option<NonNull<Node<T>>> {
+        let nn = NonNull::new(self.first_node.get_raw(Ordering::Acquire));
         trace!("first_node() = {:?}", nn);
         nn
     }
 
     /// Atomically extracts pointer to last node
     #[inline]
-    pub fn last_node(&self) -> Option<NonNull<Node<T>>> {
-        let nn = NonNull::new(self.last_node.load(Ordering::Relaxed));
+    pub(crate) fn last_node(&self) -> Option<NonNull<Node<T>>> {
+        let nn = NonNull::new(self.last_node.load(Ordering::Acquire));
         trace!("last_node() = {:?}", nn);
         nn
     }
@@ -51,7 +83,8 @@ impl<T> Inner<T> {
     /// Atomically extracts `Inner`'s size
     #[inline]
     pub fn len(&self) -> usize {
-        let len = self.size.load(Ordering::Relaxed);
+        // Pairs with the `Release` in `append_chain`, so every node counted is already linked
+        let len = self.size.load(Ordering::Acquire);
         trace!("len() = {}", len);
         len
     }
@@ -64,22 +97,122 @@ impl<T> Inner<T> {
     }
 
     /// Set first node in chain
+    ///
+    /// # Safety
+    ///
+    /// You must own `node` (and the chain after it), `Inner` takes its ownership if it succeeds
     #[inline]
-    fn set_first(&self, node: Box<Node<T>>) -> Result<(), NotEmpty> {
+    unsafe fn set_first(&self, node: NonNull<Node<T>>) -> Result<(), NotEmpty> {
         trace!("set_first({:p})", node);
-        let ret = self.first_node.try_store(node, Ordering::Relaxed);
+        let ret = self.first_node.try_store_raw(node, Ordering::Release);
         debug_assert!(ret.is_ok());
         ret
     }
 
     /// Swaps last node, returning old one
     #[inline]
-    fn swap_last(&self, ptr: *mut Node<T>) -> Option<NonNull<Node<T>>> {
+    pub(crate) fn swap_last(&self, ptr: *mut Node<T>) -> Option<NonNull<Node<T>>> {
         trace!("swap_last({:p})", ptr);
-        NonNull::new(self.last_node.swap(ptr, Ordering::Relaxed))
+        // `Acquire` lets us deref the old last node, `Release` publishes the new one
+        NonNull::new(self.last_node.swap(ptr, Ordering::AcqRel))
+    }
+
+    /// Unlinks the node at `index` from the chain, returning its value (`None` if out of bounds)
+    ///
+    /// Exclusive access ensures nobody else can be traversing the chain
+    #[inline]
+    pub(crate) fn remove(&mut self, index: usize) -> Option<T> {
+        trace!("remove({})", index);
+        if index >= self.len() {
+            return None;
+        }
+
+        let (mut previous, mut current, mut offset) = (None, self.first_node()?, index);
+        // We can deref its pointer because we own it, and nobody else can access it while we are borrowed mutably
+        while offset >= unsafe { current.as_ref() }.values().len() {
+            offset -= unsafe { current.as_ref() }.values().len();
+            previous = Some(current);
+            current = unsafe { current.as_ref() }.next_ptr()?;
+        }
+
+        *self.size.get_mut() -= 1;
+        // Bulk nodes with other values stay linked
+        if unsafe { current.as_ref() }.values().len() > 1 {
+            return Some(unsafe { &mut *current.as_ptr() }.remove_value(offset));
+        }
+
+        // Nodes are relinked through their raw pointers, so `last_node` (a copy of one of them) stays valid
+        // We can deref them because we own them, and nobody else can access them while we are borrowed mutably
+        let node = if let Some(previous) = previous {
+            let node = unsafe { &mut *previous.as_ptr() }.take_next()?;
+            if let Some(next) = unsafe { &mut *node.as_ptr() }.take_next() {
+                let _ = unsafe { previous.as_ref().try_store_next(next) };
+            } else {
+                *self.last_node.get_mut() = previous.as_ptr();
+            }
+            node
+        } else {
+            let first = self.first_node.take_raw(Ordering::Relaxed)?;
+            if let Some(next) = unsafe { &mut *first.as_ptr() }.take_next() {
+                let _ = unsafe { self.set_first(next) };
+            } else {
+                *self.last_node.get_mut() = null_mut();
+            }
+            first
+        };
+
+        debug_assert_eq!(node, current);
+        // The node was unlinked, so we own it and can `Box` it back
+        let node = unsafe { Box::from_raw(node.as_ptr()) };
+        debug_assert!(node.next().is_none());
+        Some(node.into_value())
+    }
+
+    /// Keeps only the elements that match `f` (which may mutate them), unlinking nodes left empty
+    ///
+    /// Exclusive access ensures nobody else can be traversing the chain, so it can be relinked in place
+    #[inline]
+    pub(crate) fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) {
+        trace!("retain_mut()");
+        let (mut previous, mut current) = (None::<NonNull<Node<T>>>, self.first_node());
+        while let Some(nn) = current {
+            // We can deref its pointer because we own it, and nobody else can access it while we are borrowed mutably
+            let node = unsafe { &mut *nn.as_ptr() };
+            let len = node.values().len();
+            let kept = node.retain_values(&mut f);
+            *self.size.get_mut() -= len - kept;
+            current = node.next_ptr();
+            if kept > 0 {
+                previous = Some(nn);
+                continue;
+            }
+
+            // Nodes are relinked through their raw pointers, so `last_node` stays valid
+            let next = node.take_next();
+            let unlinked = if let Some(previous) = previous {
+                let unlinked = unsafe { &mut *previous.as_ptr() }.take_next();
+                if let Some(next) = next {
+                    let _ = unsafe { previous.as_ref().try_store_next(next) };
+                }
+                unlinked
+            } else {
+                let unlinked = self.first_node.take_raw(Ordering::Relaxed);
+                if let Some(next) = next {
+                    let _ = unsafe { self.set_first(next) };
+                }
+                unlinked
+            };
+            if next.is_none() {
+                *self.last_node.get_mut() = previous.map_or(null_mut(), NonNull::as_ptr);
+            }
+
+            debug_assert_eq!(unlinked, Some(nn));
+            // The node was unlinked (and has no next), so we own it and can drop it
+            drop(unsafe { Box::from_raw(nn.as_ptr()) });
+        }
     }
 
-    /// Unsafelly append a `Node<T>` chain to `Inner<T>`
+    /// Unsafelly append a `Node<T>` chain to `Inner<T>`, returning the size right after it
     ///
     /// # Safety
     ///
@@ -91,39 +224,147 @@ impl<T> Inner<T> {
     ///
     /// (The objects pointed must exist while `Inner` exists and they can't be accessed after)
     #[inline]
-    pub unsafe fn append_chain(&self, first: *mut Node<T>, last: *mut Node<T>, length: usize) {
+    pub(crate) unsafe fn append_chain(
+        &self,
+        first: *mut Node<T>,
+        last: *mut Node<T>,
+        length: usize,
+    ) -> usize {
         debug!("append_chain({:p}, {:p}, {})", first, last, length);
+        // Size must never wrap, a small length would make iterators and snapshots skip linked nodes
+        // It's checked before linking, while we still own the chain (so it's freed and nothing changes)
+        if self.len().checked_add(length).is_none() {
+            drop(Box::from_raw(first));
+            panic!("size of Inner overflowed usize");
+        }
+
+        // The caller ensures `first` is owned by nobody else (and isn't null), so `Inner` takes its ownership
+        // Pointers are stored as is (never rebuilt into a `Box`), so `last` stays valid after linking `first`
+        let first = NonNull::new_unchecked(first);
         if let Some(nn) = self.swap_last(last) {
-            // To call `Box::from_raw` unsafe is needed
-            // But since `Inner` owns what they point to, it can be sure they will exist while `Inner` does
+            // `Inner` owns what they point to, so it can be sure they will exist while `Inner` does
             // (as long as `append_chain` was properly called)
             #[allow(unused)]
-            let old = nn.as_ref().try_store_next(Box::from_raw(first));
+            let old = nn.as_ref().try_store_next(first);
             debug_assert!(old.is_ok());
         } else {
-            // To call `Box::from_raw` you must make sure `Inner` now owns the `Node<T>`
-            let _ = self.set_first(Box::from_raw(first));
+            let _ = self.set_first(first);
         }
 
         info!("Increased size by {}", length);
-        let _ = self.size.fetch_add(length, Ordering::Relaxed);
+        // Only concurrent appends racing past the limit after the check could still wrap it
+        self.size.fetch_add(length, Ordering::Release) + length
     }
 
-    /// Appends node to end of `Inner` (inserts first_node if it's the first)
+    /// Appends node to end of `Inner` (inserts first_node if it's the first), returning the size right after it
     #[inline]
-    pub fn append(&self, value: T) {
+    pub(crate) fn append_len(&self, value: T) -> usize {
         let ptr = Node::new(value).into_ptr();
         // We own `Node<T>` so we can pass its ownership to `append_chain`
         // And we don't drop it
-        unsafe { self.append_chain(ptr, ptr, 1) };
+        unsafe { self.append_chain(ptr, ptr, 1) }
+    }
+
+    /// Appends every value in a single node, returning the size right after it (nothing is appended if `values` is empty)
+    #[inline]
+    pub(crate) fn append_values(&self, values: Box<[T]>) -> usize {
+        let length = values.len();
+        // An empty node would break the "never empty" invariant of node values
+        if length == 0 {
+            return self.len();
+        }
+        let ptr = Node::from_values(values).into_ptr();
+        // We own `Node<T>` so we can pass its ownership to `append_chain`
+        // And we don't drop it
+        unsafe { self.append_chain(ptr, ptr, length) }
+    }
+
+    /// Appends node to end of `Inner` without atomic read-modify-write operations in `size` and `last_node`
+    ///
+    /// Exclusive access ensures nobody else can be changing them
+    #[inline]
+    pub(crate) fn push(&mut self, value: T) {
+        // `Box::into_raw` never returns null
+        let node = unsafe { NonNull::new_unchecked(Node::new(value).into_ptr()) };
+        // We own `Node<T>` and `Inner` takes its ownership here
+        if let Some(nn) = NonNull::new(replace(self.last_node.get_mut(), node.as_ptr())) {
+            // We can deref its pointer because we own it, and nobody else can access it while we are borrowed mutably
+            let old = unsafe { nn.as_ref().try_store_next(node) };
+            debug_assert!(old.is_ok());
+        } else {
+            let _ = unsafe { self.set_first(node) };
+        }
+        let size = self.size.get_mut();
+        *size = size.checked_add(1).expect("size of Inner overflowed usize");
+    }
+
+    /// Returns mutable reference to the element at `index` (`None` if out of bounds)
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        trace!("get_mut({})", index);
+        if index >= self.len() {
+            return None;
+        }
+
+        let (mut node, mut offset) = (self.first_node(), index);
+        while let Some(nn) = node {
+            // Exclusive access to `Inner` means nobody else can reference its values
+            let node_mut = unsafe { &mut *nn.as_ptr() };
+            let len = node_mut.values().len();
+            if offset < len {
+                return node_mut.values_mut().get_mut(offset);
+            }
+            offset -= len;
+            node = node_mut.next_ptr();
+        }
+        None
+    }
+
+    /// Iterates over the elements linked so far, borrowing `Inner` (unlike `Iter`, that owns an `Arc`)
+    #[inline]
+    pub(crate) fn elements(&self) -> impl Iterator<Item = &T> {
+        // The chain is owned by `Inner` and nodes are never dropped while it's borrowed
+        let first = self.first_node().map(|nn| unsafe { &*nn.as_ptr() });
+        successors(first, |node| node.next()).flat_map(|node| node.values())
+    }
+
+    /// Walks the chain checking that it matches `size` and `last_node`
+    ///
+    /// Concurrent appends to this `Inner` can make it fail spuriously
+    #[inline]
+    pub(crate) fn verify_integrity(&self) -> Result<(), IntegrityError> {
+        trace!("verify_integrity()");
+        let size = self.len();
+        let (mut node, mut last, mut counted, mut nodes) = (self.first_node(), None, 0, 0);
+        while let Some(nn) = node {
+            // The chain is owned by `Inner` and nodes are never dropped while it's borrowed
+            let current = unsafe { nn.as_ref() };
+            counted += current.values().len();
+            nodes += 1;
+            if counted > size {
+                return Err(IntegrityError::TooLong { size });
+            }
+            last = Some(nn);
+            node = current.next().map(NonNull::from);
+        }
+
+        if counted < size {
+            Err(IntegrityError::TooShort { size, counted })
+        } else if last != self.last_node() {
+            Err(IntegrityError::LastNodeMismatch { nodes })
+        } else {
+            Ok(())
+        }
     }
 
     #[inline]
     /// Extracts chain and drops itself without dropping it
-    pub fn into_inner(self) -> (usize, *mut Node<T>, *mut Node<T>) {
+    pub(crate) fn into_inner(mut self) -> (usize, *mut Node<T>, *mut Node<T>) {
         trace!("into_inner()");
+        // Extracted as is (not through a `Box`), so `last` stays valid
+        let first = self.first_node.take_raw(Ordering::Relaxed);
+        let first = first.map_or(null_mut(), NonNull::as_ptr);
         let size = self.size.into_inner();
-        let first = self.first_node.into_inner().into_ptr();
         let last = self.last_node.into_inner();
         (size, first, last)
     }
@@ -133,9 +374,9 @@ impl<T> FromIterator<T> for Inner<T> {
     #[inline]
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         trace!("FromIterator<T>");
-        let inner = Self::default();
+        let mut inner = Self::default();
         for element in iter {
-            inner.append(element);
+            inner.push(element);
         }
         inner
     }
@@ -232,7 +473,14 @@ impl<T> FromIterator<T> for Inner<T> {
 ///     println!("Multi-thread example ended without errors");
 /// }
 /// ```
-pub struct VoluntaryServitude<T>(RwLock<Arc<Inner<T>>>);
+pub struct VoluntaryServitude<T>(
+    RwLock<Arc<Inner<T>>>,
+    AtomicUsize,
+    FillOnceAtomicOption<AppendCallback>,
+);
+
+/// Callback called with the new length after every append
+type AppendCallback = Arc<dyn Fn(usize) + Send + Sync>;
 
 /// [`VoluntaryServitude`]'s alias
 ///
@@ -255,6 +503,37 @@ impl<T> VoluntaryServitude<T> {
         Self::default()
     }
 
+    /// Creates new empty `VS` inside an `Arc`, ready to be shared between threads
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::VS;
+    /// # env_logger::init();
+    /// use std::{sync::Arc, thread::spawn};
+    /// let list = VS::shared();
+    /// let producer = Arc::clone(&list);
+    /// spawn(move || producer.append(1)).join().unwrap();
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1]);
+    /// ```
+    #[inline]
+    pub fn shared() -> Arc<Self> {
+        trace!("shared()");
+        Arc::new(Self::default())
+    }
+
+    /// Creates new `VS` with every element of `iter` inside an `Arc`, ready to be shared between threads
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::VS;
+    /// # env_logger::init();
+    /// let list = VS::shared_from_iter(1..4);
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    /// ```
+    #[inline]
+    pub fn shared_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Arc<Self> {
+        trace!("shared_from_iter()");
+        Arc::new(Self::from_iter(iter))
+    }
+
     /// Inserts element after last node
     ///
     /// ```rust
@@ -274,7 +553,175 @@ impl<T> VoluntaryServitude<T> {
     /// ```
     #[inline]
     pub fn append(&self, value: T) {
-        self.0.read().append(value);
+        let len = self.0.read().append_len(value);
+        self.bump_generation();
+        self.notify_append(len);
+    }
+
+    /// Registers callback called with the new length after every append (including bulk ones, like `extend`, once per call)
+    ///
+    /// Only one callback can be registered, it's called from the appending thread after the lock is released
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// use std::sync::{Arc, atomic::AtomicUsize, atomic::Ordering};
+    /// let list = vs![];
+    /// let last_len = Arc::new(AtomicUsize::new(0));
+    /// let len = Arc::clone(&last_len);
+    /// let callback = move |new_len| len.store(new_len, Ordering::Relaxed);
+    /// assert!(list.on_append(Arc::new(callback)).is_ok());
+    ///
+    /// list.append(1);
+    /// list.append(2);
+    /// assert_eq!(last_len.load(Ordering::Relaxed), 2);
+    /// assert!(list.on_append(Arc::new(|_| ())).is_err());
+    /// ```
+    #[inline]
+    pub fn on_append(&self, callback: Arc<dyn Fn(usize) + Send + Sync>) -> Result<(), NotEmpty> {
+        debug!("on_append()");
+        self.2.try_store(callback, Ordering::Release)
+    }
+
+    /// Calls append callback, if registered
+    #[inline]
+    fn notify_append(&self, len: usize) {
+        if let Some(callback) = self.2.get_ref(Ordering::Acquire) {
+            trace!("notify_append({})", len);
+            callback(len);
+        }
+    }
+
+    /// Increments generation, must be called after every mutation
+    #[inline]
+    fn bump_generation(&self) {
+        let _ = self.1.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns current generation, it's incremented by every mutation (like `append`, `extend`, `clear` and `swap`)
+    ///
+    /// Comparing it before and after some work detects if `VS` changed in between, without diffing its elements
+    ///
+    /// `Relaxed` ordering is used, so you shouldn't depend on this being sequentially consistent, only atomic
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![];
+    /// let generation = list.generation();
+    /// let _ = list.iter().count();
+    /// assert_eq!(list.generation(), generation);
+    ///
+    /// list.append(3);
+    /// assert_ne!(list.generation(), generation);
+    /// ```
+    #[inline]
+    pub fn generation(&self) -> usize {
+        let generation = self.1.load(Ordering::Relaxed);
+        trace!("generation() = {}", generation);
+        generation
+    }
+
+    /// Returns the `Arc` holding the list's current chain (the same handle `iter` wraps)
+    ///
+    /// It keeps the whole chain alive (even after the list is cleared), just like an `Iter` does
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![3, 2];
+    /// let snapshot = list.snapshot_arc();
+    /// list.clear();
+    /// assert_eq!(snapshot.len(), 2);
+    /// assert_eq!(list.snapshot_arc().len(), 0);
+    /// ```
+    #[inline]
+    pub fn snapshot_arc(&self) -> Arc<Inner<T>> {
+        debug!("snapshot_arc()");
+        Arc::clone(&*self.0.read())
+    }
+
+    /// Recovers exclusive ownership of the elements if no `Iter` (or snapshot) references them, otherwise returns `VS` back
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2];
+    /// let iter = list.iter();
+    /// let list = list.try_into_unique().unwrap_err();
+    /// drop(iter);
+    ///
+    /// let mut unique = list.try_into_unique().unwrap();
+    /// *unique.get_mut(0).unwrap() = 3;
+    /// let list = unique.into_shared();
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &2]);
+    /// ```
+    #[inline]
+    pub fn try_into_unique(self) -> Result<UniqueVS<T>, Self> {
+        debug!("try_into_unique()");
+        let VoluntaryServitude(inner, generation, callback) = self;
+        let generation = generation.into_inner();
+        match Arc::try_unwrap(inner.into_inner()) {
+            Ok(inner) => Ok(UniqueVS(inner, generation, callback)),
+            Err(arc) => Err(VoluntaryServitude(
+                RwLock::new(arc),
+                AtomicUsize::new(generation),
+                callback,
+            )),
+        }
+    }
+
+    /// Walks the current chain checking that its length and last node match the ones stored (to debug corruption)
+    ///
+    /// The write lock is held during the walk, so appends made through this `VS` wait for it (appends through other handles sharing the chain may cause spurious errors)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2, 3];
+    /// list.extend_from_slice(&[4, 5]);
+    /// assert_eq!(list.verify_integrity(), Ok(()));
+    /// ```
+    #[inline]
+    pub fn verify_integrity(&self) -> Result<(), IntegrityError> {
+        debug!("verify_integrity()");
+        self.0.write().verify_integrity()
+    }
+
+    /// Makes immutable snapshot of `VS` that can be indexed and iterated in both directions
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![3, 2];
+    /// let frozen = list.freeze();
+    /// list.append(1);
+    /// assert_eq!(frozen.len(), 2);
+    /// assert_eq!(frozen[1], 2);
+    /// ```
+    #[inline]
+    pub fn freeze(&self) -> Frozen<T> {
+        debug!("freeze()");
+        Frozen::from(self.snapshot_arc())
+    }
+
+    /// Searches for the last element that satisfies `f`, returning its index
+    ///
+    /// A singly linked chain can't be walked backwards, so the current snapshot is [`freeze`]d first (`O(n)`)
+    ///
+    /// [`freeze`]: #method.freeze
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2, 3, 2];
+    /// assert_eq!(list.rposition(|&v| v == 2), Some(3));
+    /// assert_eq!(list.rposition(|&v| v == 5), None);
+    /// ```
+    #[inline]
+    pub fn rposition<F: FnMut(&T) -> bool>(&self, f: F) -> Option<usize> {
+        debug!("rposition()");
+        self.freeze().iter().rposition(f)
     }
 
     /// Makes lock-free iterator based on `VS`
@@ -288,6 +735,20 @@ impl<T> VoluntaryServitude<T> {
     /// for (element, expected) in list.iter().zip(&[3, 2][..]) {
     ///     assert_eq!(element, expected);
     /// }
+    ///
+    /// let mut sum = 0;
+    /// for element in &mut list.iter() {
+    ///     sum += element;
+    /// }
+    /// assert_eq!(sum, 5);
+    /// ```
+    ///
+    /// `&VS` isn't `IntoIterator`: elements are kept alive by the `Iter`, not by the borrow of `VS` (that can be cleared through `&self`), so references can't outlive the `Iter`
+    ///
+    /// ```rust,compile_fail
+    /// # use voluntary_servitude::vs;
+    /// let list = vs![3, 2];
+    /// for element in &list {}
     /// ```
     #[inline]
     pub fn iter(&self) -> Iter<T> {
@@ -297,7 +758,9 @@ impl<T> VoluntaryServitude<T> {
 
     /// Returns current size, be careful with race conditions when using it since other threads can change it right after the read
     ///
-    /// `Relaxed` ordering is used to extract the length, so you shouldn't depend on this being sequentially consistent, only atomic
+    /// `Acquire` ordering is used to extract the length, so you shouldn't depend on this being sequentially consistent, only atomic
+    ///
+    /// It pairs with the `Release` of the append, so every element counted is visible to iterators created afterwards (in any thread)
     ///
     /// ```rust
     /// # use voluntary_servitude::vs;
@@ -314,9 +777,50 @@ impl<T> VoluntaryServitude<T> {
         self.0.read().len()
     }
 
+    /// Estimates heap bytes used by the current chain: one `Node<T>` per element plus the shared `Inner` (and its `Arc` counters)
+    ///
+    /// It's a shallow estimate, memory owned by the elements (like a `String`'s buffer) and chains kept alive by old iterators aren't counted
+    ///
+    /// Elements appended with `extend_from_slice` share a node, so they are overestimated
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1u64];
+    /// let one = list.approx_heap_bytes();
+    /// list.append(2);
+    /// assert!(list.approx_heap_bytes() > one);
+    /// ```
+    #[inline]
+    pub fn approx_heap_bytes(&self) -> usize {
+        let len = self.len();
+        trace!("approx_heap_bytes() with {} elements", len);
+        len * size_of::<Node<T>>() + size_of::<Inner<T>>() + 2 * size_of::<AtomicUsize>()
+    }
+
+    /// Counts handles keeping the current chain alive besides this `VS` (so a `clear` will only free it if it's `0`)
+    ///
+    /// Every `Iter`, `FollowIter`, `Frozen`, snapshot and `VS` sharing the chain is counted
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2];
+    /// let iter = list.iter();
+    /// assert_eq!(list.outstanding_iters(), 1);
+    /// drop(iter);
+    /// assert_eq!(list.outstanding_iters(), 0);
+    /// ```
+    #[inline]
+    pub fn outstanding_iters(&self) -> usize {
+        let count = Arc::strong_count(&*self.0.read()) - 1;
+        trace!("outstanding_iters() = {}", count);
+        count
+    }
+
     /// Checks if `VS` is currently empty, be careful with race conditions when using it since other threads can change it right after the read
     ///
-    /// `Relaxed` ordering is used to extract the length, so you shouldn't depend on this being sequentially consistent, only atomic
+    /// `Acquire` ordering is used to extract the length, so you shouldn't depend on this being sequentially consistent, only atomic
     ///
     /// ```rust
     /// # use voluntary_servitude::vs;
@@ -347,6 +851,36 @@ impl<T> VoluntaryServitude<T> {
     pub fn clear(&self) {
         debug!("clear()");
         *self.0.write() = Arc::new(Inner::default());
+        self.bump_generation();
+    }
+
+    /// Clears list if the lock can be acquired without blocking, returns `false` if it's contended
+    ///
+    /// The lock is contended while other threads are appending, creating iterators or clearing/swapping the list
+    ///
+    /// Returning `false` means nothing was cleared (iterators referencing the old chain will still work either way)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![3, 2];
+    /// let iter = list.iter();
+    /// assert!(list.try_clear());
+    /// assert_eq!(iter.len(), 2);
+    /// assert_eq!(list.len(), 0);
+    /// ```
+    #[inline]
+    pub fn try_clear(&self) -> bool {
+        debug!("try_clear()");
+        let cleared = self
+            .0
+            .try_write()
+            .map(|mut inner| *inner = Arc::new(Inner::default()))
+            .is_some();
+        if cleared {
+            self.bump_generation();
+        }
+        cleared
     }
 
     /// Clears list returning iterator to it (other iterators referencing the old chain will still work)
@@ -383,10 +917,14 @@ impl<T> VoluntaryServitude<T> {
     pub fn swap(&self, other: &Self) {
         debug!("swap({:p})", other);
         swap(&mut *self.0.write(), &mut *other.0.write());
+        self.bump_generation();
+        other.bump_generation();
     }
 
     /// Extends `VS` like the `Extend` trait, but without a mutable reference
     ///
+    /// It's all-or-nothing: elements are chained locally and spliced once at the end, so if `iter` panics the list is left unchanged (and the elements already produced are dropped)
+    ///
     /// ```rust
     /// # use voluntary_servitude::vs;
     /// # env_logger::init();
@@ -405,114 +943,2600 @@ impl<T> VoluntaryServitude<T> {
     #[inline]
     pub fn extend<I: IntoIterator<Item = T>>(&self, iter: I) {
         trace!("extend()");
-        let (size, first, last) = Inner::from_iter(iter).into_inner();
-        // We own `Inner<T>` so we can pass its ownership of its nodes to `append_chain`
-        // And we don't drop them
-        unsafe { self.0.read().append_chain(first, last, size) };
+        self.append_inner(Inner::from_iter(iter));
     }
-}
 
-impl<T> Default for VoluntaryServitude<T> {
+    /// Extends `VS` with the `Ok` values, stopping at the first `Err` and returning it
+    ///
+    /// The values before the error are still appended (spliced together, like `extend`), the ones after it aren't consumed
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1];
+    /// assert_eq!(list.try_extend(vec![Ok(2), Ok(3)]), Ok::<(), &str>(()));
+    /// assert_eq!(list.try_extend(vec![Ok(4), Err("bad"), Ok(5)]), Err("bad"));
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+    /// ```
     #[inline]
-    fn default() -> Self {
-        trace!("default()");
-        Self::from(Inner::default())
+    pub fn try_extend<E, I: IntoIterator<Item = Result<T, E>>>(&self, iter: I) -> Result<(), E> {
+        trace!("try_extend()");
+        let mut error = None;
+        let values = iter
+            .into_iter()
+            .map_while(|result| result.map_err(|err| error = Some(err)).ok());
+        self.append_inner(Inner::from_iter(values));
+        error.map_or(Ok(()), Err)
     }
-}
 
-impl<T: Debug> Debug for VoluntaryServitude<T> {
+    /// Splices `inner`'s chain after last node
     #[inline]
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        f.debug_tuple("VoluntaryServitude")
-            .field(&self.iter().collect::<Vec<_>>())
-            .finish()
+    pub(crate) fn append_inner(&self, inner: Inner<T>) {
+        let (size, first, last) = inner.into_inner();
+        // An empty chain would replace the last node with `null`
+        if size == 0 {
+            return;
+        }
+        // We own `Inner<T>` so we can pass its ownership of its nodes to `append_chain`
+        // And we don't drop them
+        let len = unsafe { self.0.read().append_chain(first, last, size) };
+        self.bump_generation();
+        self.notify_append(len);
     }
-}
 
-impl<T> Extend<T> for VoluntaryServitude<T> {
+    /// Creates a [`Batch`] that buffers values locally and appends all of them at once
+    ///
+    /// The buffered values are appended when the `Batch` is committed or dropped
+    ///
+    /// [`Batch`]: ./struct.Batch.html
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1];
+    /// let mut batch = list.batch();
+    /// batch.push(2);
+    /// batch.push(3);
+    /// assert_eq!(list.len(), 1);
+    ///
+    /// batch.commit();
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    /// ```
     #[inline]
-    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        Self::extend(self, iter)
+    pub fn batch(&self) -> Batch<'_, T> {
+        debug!("batch()");
+        Batch::from(self)
     }
-}
 
-impl<'a, T: 'a + Copy> Extend<&'a T> for VoluntaryServitude<T> {
+    /// Appends copies of every element in `slice` at once, stored in a single node (instead of one node per element)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2];
+    /// list.extend_from_slice(&[3, 4]);
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+    /// ```
     #[inline]
-    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
-        Self::extend(self, iter.into_iter().cloned())
+    pub fn extend_from_slice(&self, slice: &[T])
+    where
+        T: Copy,
+    {
+        trace!("extend_from_slice({})", slice.len());
+        if slice.is_empty() {
+            return;
+        }
+        let len = self.0.read().append_values(slice.into());
+        self.bump_generation();
+        self.notify_append(len);
     }
-}
 
-impl<T> FromIterator<T> for VoluntaryServitude<T> {
+    /// Returns mutable reference to the element at `index`
+    ///
+    /// Only possible if nobody else references the list's chain (no `Iter` alive), otherwise returns `None` (also if out of bounds)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let mut list = vs![1, 2, 3];
+    /// *list.get_mut(1).unwrap() = 5;
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &5, &3]);
+    /// assert_eq!(list.get_mut(3), None);
+    ///
+    /// let iter = list.iter();
+    /// assert_eq!(list.get_mut(0), None);
+    /// drop(iter);
+    /// assert_eq!(list.get_mut(0), Some(&mut 1));
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        trace!("get_mut({})", index);
+        let value = Arc::get_mut(self.0.get_mut()).and_then(|inner| inner.get_mut(index));
+        if value.is_some() {
+            // The element may be mutated through the returned reference
+            *self.1.get_mut() = self.1.get_mut().wrapping_add(1);
+        }
+        value
+    }
+
+    /// Folds every element into an accumulator, like `Iterator::fold`, but without a `&mut Iter`
+    ///
+    /// The length is read once before folding, so it reflects a single point-in-time view (elements appended while folding are ignored)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2, 3, 4];
+    /// assert_eq!(list.fold(0, |acc, el| acc + el), 10);
+    /// ```
+    #[inline]
+    pub fn fold<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+        trace!("fold()");
+        let iter = &mut self.iter();
+        let len = iter.len();
+        iter.take(len).fold(init, f)
+    }
+}
+
+impl<T: Clone> VoluntaryServitude<T> {
+    /// Clones first element in list
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![3, 2, 1];
+    /// assert_eq!(list.first(), Some(3));
+    /// list.clear();
+    /// assert_eq!(list.first(), None);
+    /// ```
+    #[inline]
+    pub fn first(&self) -> Option<T> {
+        trace!("first()");
+        (&mut self.iter()).next().cloned()
+    }
+
+    /// Clones last element in list
+    ///
+    /// `Acquire` ordering is used to extract the last node, so you shouldn't depend on this being sequentially consistent
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![3, 2, 1];
+    /// assert_eq!(list.last(), Some(1));
+    /// list.clear();
+    /// assert_eq!(list.last(), None);
+    /// ```
+    #[inline]
+    pub fn last(&self) -> Option<T> {
+        trace!("last()");
+        self.iter().last_node().cloned()
+    }
+
+    /// Clones greatest element in the current snapshot according to `f`, like `Iterator::max_by` (last one on ties)
+    ///
+    /// Elements appended while it's searching may or may not be considered
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![-3i32, 2, -1];
+    /// assert_eq!(list.max_by(|a, b| a.abs().cmp(&b.abs())), Some(-3));
+    /// list.clear();
+    /// assert_eq!(list.max_by(|a, b| a.cmp(b)), None);
+    /// ```
+    #[inline]
+    pub fn max_by<F: FnMut(&T, &T) -> CmpOrdering>(&self, mut f: F) -> Option<T> {
+        trace!("max_by()");
+        (&mut self.iter()).max_by(|a, b| f(a, b)).cloned()
+    }
+
+    /// Clones smallest element in the current snapshot according to `f`, like `Iterator::min_by` (first one on ties)
+    ///
+    /// Elements appended while it's searching may or may not be considered
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![-3i32, 2, -1];
+    /// assert_eq!(list.min_by(|a, b| a.abs().cmp(&b.abs())), Some(-1));
+    /// list.clear();
+    /// assert_eq!(list.min_by(|a, b| a.cmp(b)), None);
+    /// ```
+    #[inline]
+    pub fn min_by<F: FnMut(&T, &T) -> CmpOrdering>(&self, mut f: F) -> Option<T> {
+        trace!("min_by()");
+        (&mut self.iter()).min_by(|a, b| f(a, b)).cloned()
+    }
+
+    /// Clones greatest element in the current snapshot (see [`max_by`])
+    ///
+    /// Not called `max` because `Ord::max` (comparing whole lists) would shadow it
+    ///
+    /// [`max_by`]: #method.max_by
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 3, 2];
+    /// assert_eq!(list.max_element(), Some(3));
+    /// ```
+    #[inline]
+    pub fn max_element(&self) -> Option<T>
+    where
+        T: Ord,
+    {
+        self.max_by(T::cmp)
+    }
+
+    /// Clones smallest element in the current snapshot (see [`min_by`])
+    ///
+    /// Not called `min` because `Ord::min` (comparing whole lists) would shadow it
+    ///
+    /// [`min_by`]: #method.min_by
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![2, 1, 3];
+    /// assert_eq!(list.min_element(), Some(1));
+    /// ```
+    #[inline]
+    pub fn min_element(&self) -> Option<T>
+    where
+        T: Ord,
+    {
+        self.min_by(T::cmp)
+    }
+
+    /// Appends clones of `other`'s current elements as a single chain (like `extend`)
+    ///
+    /// `other`'s length is read once, so appending to itself only duplicates the current elements
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2];
+    /// list.extend_from(&vs![3, 4]);
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+    /// ```
+    #[inline]
+    pub fn extend_from(&self, other: &Self) {
+        debug!("extend_from({:p})", other);
+        let inner = other.snapshot_arc();
+        let len = inner.len();
+        let iter = &mut Iter::from(inner);
+        self.append_inner(Inner::from_iter(iter.take(len).cloned()));
+    }
+
+    /// Makes lock-free iterator that yields clones of the elements (like `iter().cloned()` without borrowing the `Iter`)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2];
+    /// let cloned = list.iter_cloned();
+    /// list.clear();
+    /// assert_eq!(cloned.collect::<Vec<_>>(), vec![1, 2]);
+    /// ```
+    #[inline]
+    pub fn iter_cloned(&self) -> impl Iterator<Item = T> {
+        debug!("iter_cloned()");
+        let mut iter = self.iter();
+        from_fn(move || (&mut iter).next().cloned())
+    }
+
+    /// Clones up to `limit` elements starting at `offset` (clamped to the list's length)
+    ///
+    /// The length is read once, so it reflects a single point-in-time view (elements appended meanwhile are ignored)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2, 3, 4, 5];
+    /// assert_eq!(list.page(0, 2), vec![1, 2]);
+    /// assert_eq!(list.page(4, 2), vec![5]);
+    /// assert_eq!(list.page(5, 2), Vec::<i32>::new());
+    /// ```
+    #[inline]
+    pub fn page(&self, offset: usize, limit: usize) -> Vec<T> {
+        trace!("page({}, {})", offset, limit);
+        let inner = self.snapshot_arc();
+        let limit = min(limit, inner.len().saturating_sub(offset));
+        let iter = &mut Iter::iter_from(inner, offset);
+        iter.take(limit).cloned().collect()
+    }
+
+    /// Clones the current snapshot into a boxed slice (more compact than a `Vec` for a fixed snapshot)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2, 3];
+    /// let boxed = list.to_boxed_slice();
+    /// list.append(4);
+    /// assert_eq!(&*boxed, &[1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn to_boxed_slice(&self) -> Box<[T]> {
+        debug!("to_boxed_slice()");
+        let iter = &mut self.iter();
+        let len = iter.len();
+        iter.take(len).cloned().collect()
+    }
+
+    /// Yields clones of the current snapshot's elements from newest to oldest
+    ///
+    /// A singly linked chain can't be walked backwards, so the snapshot is [`freeze`]d first (`O(n)`), elements appended after it's called aren't yielded
+    ///
+    /// [`freeze`]: #method.freeze
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2, 3];
+    /// let recent_first = list.iter_rev_cloned();
+    /// list.append(4);
+    /// assert_eq!(recent_first.collect::<Vec<_>>(), vec![3, 2, 1]);
+    /// ```
+    #[inline]
+    pub fn iter_rev_cloned(&self) -> impl Iterator<Item = T> {
+        debug!("iter_rev_cloned()");
+        let frozen = self.freeze();
+        (0..frozen.len())
+            .rev()
+            .map(move |index| frozen[index].clone())
+    }
+
+    /// Replaces `buf`'s contents with clones of the current snapshot's elements, keeping its capacity (to avoid allocating when polling)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2];
+    /// let mut buf = vec![9, 9, 9];
+    /// list.clone_into_vec(&mut buf);
+    /// assert_eq!(buf, vec![1, 2]);
+    /// ```
+    #[inline]
+    pub fn clone_into_vec(&self, buf: &mut Vec<T>) {
+        debug!("clone_into_vec()");
+        buf.clear();
+        let iter = &mut self.iter();
+        let len = iter.len();
+        buf.extend(iter.take(len).cloned());
+    }
+
+    /// Splits current snapshot in `Vec`s of `size` cloned elements (the last one may be shorter)
+    ///
+    /// Elements appended after it's called aren't yielded
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is `0`
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2, 3, 4, 5];
+    /// let chunks = list.chunks_owned(2);
+    /// list.append(6);
+    /// assert_eq!(chunks.collect::<Vec<_>>(), vec![vec![1, 2], vec![3, 4], vec![5]]);
+    /// ```
+    #[inline]
+    pub fn chunks_owned(&self, size: usize) -> impl Iterator<Item = Vec<T>> {
+        trace!("chunks_owned({})", size);
+        assert!(size > 0, "chunks_owned size must be greater than 0");
+        let mut iter = self.iter();
+        let mut remaining = iter.len();
+        from_fn(move || {
+            let chunk = min(size, remaining);
+            remaining -= chunk;
+            Some((&mut iter).take(chunk).cloned().collect()).filter(|_| chunk > 0)
+        })
+    }
+
+    /// Clones every `step`-th element of the current snapshot, starting with the first one (like `step_by`)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is `0`
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2, 3, 4, 5];
+    /// assert_eq!(list.sample_every(2), vec![1, 3, 5]);
+    /// ```
+    #[inline]
+    pub fn sample_every(&self, step: usize) -> Vec<T> {
+        debug!("sample_every({})", step);
+        assert!(step > 0, "sample_every step must be greater than 0");
+        let inner = self.snapshot_arc();
+        let len = inner.len();
+        let iter = &mut Iter::from(inner);
+        iter.take(len).step_by(step).cloned().collect()
+    }
+
+    /// Clones the current snapshot into two new lists, the elements that match `f` and the ones that don't (like `Iterator::partition`)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2, 3, 4];
+    /// let (even, odd) = list.partition(|n| n % 2 == 0);
+    /// assert_eq!(even, vs![2, 4]);
+    /// assert_eq!(odd, vs![1, 3]);
+    /// ```
+    #[inline]
+    pub fn partition<F: FnMut(&T) -> bool>(&self, mut f: F) -> (Self, Self) {
+        debug!("partition()");
+        let (mut matching, mut other) = (Inner::default(), Inner::default());
+        let iter = &mut self.iter();
+        let len = iter.len();
+        for value in iter.take(len) {
+            if f(value) {
+                matching.push(value.clone());
+            } else {
+                other.push(value.clone());
+            }
+        }
+        (Self::from(matching), Self::from(other))
+    }
+
+    /// Keeps only the first `len` elements, rebuilding the list with their clones (no-op if it isn't longer than `len`)
+    ///
+    /// The write lock is held while the new chain is built, so concurrent appends wait for it to end instead of being lost
+    ///
+    /// Iterators created before truncating keep referencing the old chain
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2, 3, 4];
+    /// let iter = list.iter();
+    /// list.truncate(2);
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2]);
+    /// assert_eq!(iter.len(), 4);
+    /// ```
+    #[inline]
+    pub fn truncate(&self, len: usize) {
+        debug!("truncate({})", len);
+        let mut inner = self.0.write();
+        if inner.len() <= len {
+            return;
+        }
+
+        let iter = &mut Iter::from(Arc::clone(&*inner));
+        *inner = Arc::new(Inner::from_iter(iter.take(len).cloned()));
+        self.bump_generation();
+    }
+
+    /// Rebuilds the list as a single contiguous block of clones (one node), to improve locality after heavy churn
+    ///
+    /// Clones are made without holding the lock, concurrent changes are handled like in [`retain_count`]
+    ///
+    /// Iterators created before consolidating keep referencing the old chain
+    ///
+    /// [`retain_count`]: #method.retain_count
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2, 3];
+    /// let iter = list.iter();
+    /// list.consolidate();
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    /// assert_eq!(iter.len(), 3);
+    /// ```
+    #[inline]
+    pub fn consolidate(&self) {
+        debug!("consolidate()");
+        let snapshot = self.snapshot_arc();
+        if snapshot.is_empty() {
+            return;
+        }
+
+        let _ = self.rebuild_from(&snapshot, true, |kept, value| kept.push(value.clone()));
+    }
+
+    /// Keeps only the last `k` elements (like a ring buffer capped at `k`), rebuilding the chain from their clones
+    ///
+    /// The write lock is held while the new chain is built, so concurrent appends wait for it to end instead of being lost
+    ///
+    /// Iterators created before it keep referencing the old chain
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2, 3, 4];
+    /// list.keep_last(2);
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&3, &4]);
+    /// ```
+    #[inline]
+    pub fn keep_last(&self, k: usize) {
+        debug!("keep_last({})", k);
+        let mut inner = self.0.write();
+        let len = inner.len();
+        if len <= k {
+            return;
+        }
+
+        let iter = &mut Iter::iter_from(Arc::clone(&*inner), len - k);
+        *inner = Arc::new(Inner::from_iter(iter.cloned()));
+        self.bump_generation();
+    }
+
+    /// Replaces elements with clones of `other`'s current snapshot (like `clone_from`, but without a mutable reference)
+    ///
+    /// `other` isn't changed and unlike [`swap`] the chains aren't exchanged, iterators created before it keep referencing the old chain
+    ///
+    /// [`swap`]: #method.swap
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2];
+    /// let other = vs![3, 4, 5];
+    /// list.set_contents(&other);
+    /// assert_eq!(list, vs![3, 4, 5]);
+    /// other.append(6);
+    /// assert_eq!(list.len(), 3);
+    /// ```
+    #[inline]
+    pub fn set_contents(&self, other: &Self) {
+        debug!("set_contents({:p})", other);
+        let iter = &mut other.iter();
+        let len = iter.len();
+        let cloned: Inner<T> = iter.take(len).cloned().collect();
+        *self.0.write() = Arc::new(cloned);
+        self.bump_generation();
+    }
+
+    /// Keeps only the elements for which `f` returns `true`, returning how many were removed
+    ///
+    /// `f` runs without holding the lock (so it can use the list), it's only taken to swap the rebuilt chain in:
+    /// elements appended meanwhile are filtered too, but if the list is cleared or swapped meanwhile it's left as is (and `0` is returned)
+    ///
+    /// Iterators created before it keep referencing the old chain
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2, 3, 4];
+    /// assert_eq!(list.retain_count(|v| v % 2 == 0), 2);
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &4]);
+    /// ```
+    #[inline]
+    pub fn retain_count<F: FnMut(&T) -> bool>(&self, mut f: F) -> usize {
+        debug!("retain_count()");
+        let snapshot = self.snapshot_arc();
+        self.rebuild_from(&snapshot, false, |kept, value| {
+            if f(value) {
+                kept.push(value.clone());
+            }
+        })
+    }
+
+    /// Rebuilds the chain from the values `f` pushes (it's called with each element of `snapshot` and the values kept so far), returning how many elements were dropped
+    ///
+    /// `f` runs without holding the lock, which is only taken to swap the rebuilt chain in (as a single node)
+    ///
+    /// Elements appended to `snapshot` meanwhile are passed to `f` in another round, if it was replaced (`clear`, `swap`...) nothing changes
+    ///
+    /// Unless `force` is set, the chain is only swapped if some element was dropped
+    #[inline]
+    fn rebuild_from<'a, F>(&self, snapshot: &'a Arc<Inner<T>>, force: bool, mut f: F) -> usize
+    where
+        F: FnMut(&mut Vec<T>, &'a T),
+    {
+        let (mut kept, mut done) = (Vec::new(), 0);
+        loop {
+            let len = snapshot.len();
+            for value in snapshot.elements().skip(done).take(len - done) {
+                f(&mut kept, value);
+            }
+            done = len;
+
+            let mut inner = self.0.write();
+            if !Arc::ptr_eq(&*inner, snapshot) {
+                return 0;
+            } else if inner.len() > done {
+                continue;
+            }
+
+            let removed = done - kept.len();
+            if removed > 0 || force {
+                let rebuilt = Inner::default();
+                let _ = rebuilt.append_values(kept.into_boxed_slice());
+                *inner = Arc::new(rebuilt);
+                self.bump_generation();
+            }
+            return removed;
+        }
+    }
+
+    /// Keeps only the first occurrence of each value (duplicates don't need to be consecutive), preserving order
+    ///
+    /// Every element is compared with the ones kept, so it's `O(n²)`, prefer [`unique_hashed`] if `T: Hash + Eq`
+    ///
+    /// Comparisons run without holding the lock, concurrent changes are handled like in [`retain_count`]
+    ///
+    /// [`unique_hashed`]: #method.unique_hashed
+    /// [`retain_count`]: #method.retain_count
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1.0, 2.0, 1.0, 3.0, 2.0];
+    /// list.unique();
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1.0, &2.0, &3.0]);
+    /// ```
+    #[inline]
+    pub fn unique(&self)
+    where
+        T: PartialEq,
+    {
+        debug!("unique()");
+        let snapshot = self.snapshot_arc();
+        let _ = self.rebuild_from(&snapshot, false, |kept, value| {
+            if !kept.contains(value) {
+                kept.push(value.clone());
+            }
+        });
+    }
+
+    /// Keeps only the first occurrence of each value (duplicates don't need to be consecutive), preserving order
+    ///
+    /// Values seen are kept in a `HashSet`, so it's `O(n)` (but stores a reference per unique value), otherwise like [`unique`]
+    ///
+    /// [`unique`]: #method.unique
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2, 1, 3, 2];
+    /// list.unique_hashed();
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    /// ```
+    #[inline]
+    pub fn unique_hashed(&self)
+    where
+        T: Hash + Eq,
+    {
+        debug!("unique_hashed()");
+        let snapshot = self.snapshot_arc();
+        let mut seen = HashSet::new();
+        let _ = self.rebuild_from(&snapshot, false, |kept, value| {
+            if seen.insert(value) {
+                kept.push(value.clone());
+            }
+        });
+    }
+}
+
+impl<K: PartialEq, V: Clone> VoluntaryServitude<(K, V)> {
+    /// Clones the most recent value appended for `key`, scanning the current snapshot from the end (like a commit log)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let log = vs![("a", 1), ("b", 2)];
+    /// log.append(("a", 3));
+    /// assert_eq!(log.latest(&"a"), Some(3));
+    /// assert_eq!(log.latest(&"b"), Some(2));
+    /// assert_eq!(log.latest(&"c"), None);
+    /// ```
+    #[inline]
+    pub fn latest(&self, key: &K) -> Option<V> {
+        debug!("latest()");
+        let frozen = self.freeze();
+        let mut entries = frozen.iter().rev();
+        entries.find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    }
+}
+
+impl<T: Clone> VoluntaryServitude<VoluntaryServitude<T>> {
+    /// Clones every inner list's snapshot into a single flat list, in order (like merging per-shard logs)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let shards = vs![vs![1, 2], vs![3, 4]];
+    /// assert_eq!(shards.flatten(), vs![1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    pub fn flatten(&self) -> VoluntaryServitude<T> {
+        debug!("flatten()");
+        let flat = VoluntaryServitude::default();
+        for list in self.freeze().iter() {
+            let iter = &mut list.iter();
+            let len = iter.len();
+            flat.append_inner(Inner::from_iter(iter.take(len).cloned()));
+        }
+        flat
+    }
+}
+
+impl VoluntaryServitude<u8> {
+    /// Writes bytes of the current snapshot to `writer` (buffered in chunks), returning how many were written
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![b'a', b'b'];
+    /// let mut file = Vec::new();
+    /// assert_eq!(list.write_to(&mut file).unwrap(), 2);
+    /// assert_eq!(file, b"ab");
+    /// ```
+    #[inline]
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<usize> {
+        debug!("write_to()");
+        let inner = self.snapshot_arc();
+        let len = inner.len();
+        let iter = &mut Iter::from(inner);
+        let mut bytes = iter.take(len).cloned();
+        let mut buffer = [0; 8 * 1024];
+        let mut written = 0;
+        loop {
+            let chunk = buffer
+                .iter_mut()
+                .zip(&mut bytes)
+                .fold(0, |size, (slot, byte)| {
+                    *slot = byte;
+                    size + 1
+                });
+            if chunk == 0 {
+                return Ok(written);
+            }
+            writer.write_all(&buffer[..chunk])?;
+            written += chunk;
+        }
+    }
+}
+
+impl<T: Ord> VoluntaryServitude<T> {
+    /// Binary searches a sorted list for `target`, like `slice::binary_search`
+    ///
+    /// Returns `Ok` with the index of a matching element or `Err` with the index where it could be inserted keeping the order
+    ///
+    /// The current snapshot is [`freeze`]d first to be indexable, that `O(n)` cost dominates the `O(log n)` search
+    ///
+    /// [`freeze`]: #method.freeze
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 3, 5, 7];
+    /// assert_eq!(list.binary_search(&5), Ok(2));
+    /// assert_eq!(list.binary_search(&4), Err(2));
+    /// ```
+    #[inline]
+    pub fn binary_search(&self, target: &T) -> Result<usize, usize> {
+        debug!("binary_search()");
+        let frozen = self.freeze();
+        let (mut low, mut high) = (0, frozen.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match frozen[middle].cmp(target) {
+                CmpOrdering::Less => low = middle + 1,
+                CmpOrdering::Greater => high = middle,
+                CmpOrdering::Equal => return Ok(middle),
+            }
+        }
+        Err(low)
+    }
+}
+
+impl<T: PartialEq> VoluntaryServitude<T> {
+    /// Inserts element after last node if it isn't already in the list, returns if it was inserted
+    ///
+    /// It's best-effort dedup: the check and the append aren't atomic together, so two threads may both find `value` absent and both insert it
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2];
+    /// assert!(list.append_if_absent(3));
+    /// assert!(!list.append_if_absent(2));
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    /// ```
+    #[inline]
+    pub fn append_if_absent(&self, value: T) -> bool {
+        debug!("append_if_absent()");
+        if (&mut self.iter()).any(|v| *v == value) {
+            return false;
+        }
+        self.append(value);
+        true
+    }
+}
+
+impl<T> VoluntaryServitude<Weak<T>> {
+    /// Inserts a `Weak` reference to `value` after last node, it won't keep `value` alive
+    ///
+    /// Use [`live_iter`] to iterate only over the values that weren't dropped
+    ///
+    /// [`live_iter`]: #method.live_iter
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// use std::sync::Arc;
+    /// let value = Arc::new(3);
+    /// let list = vs![];
+    /// list.append_weak(&value);
+    /// assert_eq!(list.live_iter().next(), Some(Arc::new(3)));
+    ///
+    /// drop(value);
+    /// assert_eq!(list.live_iter().next(), None);
+    /// assert_eq!(list.len(), 1);
+    /// ```
+    #[inline]
+    pub fn append_weak(&self, value: &Arc<T>) {
+        trace!("append_weak()");
+        self.append(Arc::downgrade(value));
+    }
+
+    /// Rebuilds list keeping only the `Weak<T>` that can still be upgraded (reclaiming the tombstones)
+    ///
+    /// The new chain is built from a snapshot without holding the lock, which is only taken to swap it in (if some tombstone was removed):
+    /// entries appended meanwhile are compacted too, but if the list is cleared or swapped meanwhile it's left as is
+    ///
+    /// Iterators created before compacting keep referencing the old chain, tombstones included
+    ///
+    /// Values dropped right after their entry is copied will stay as tombstones until the next compaction
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// use std::sync::Arc;
+    /// let (a, b) = (Arc::new(1), Arc::new(2));
+    /// let list = vs![];
+    /// list.append_weak(&a);
+    /// list.append_weak(&b);
+    /// let iter = list.iter();
+    ///
+    /// drop(a);
+    /// list.compact_tombstones();
+    /// assert_eq!(list.len(), 1);
+    /// assert_eq!(iter.len(), 2);
+    /// ```
+    #[inline]
+    pub fn compact_tombstones(&self) {
+        debug!("compact_tombstones()");
+        let snapshot = self.snapshot_arc();
+        let _ = self.rebuild_from(&snapshot, false, |kept, weak| {
+            if weak.strong_count() > 0 {
+                kept.push(Weak::clone(weak));
+            }
+        });
+    }
+}
+
+impl<T> Default for VoluntaryServitude<T> {
+    #[inline]
+    fn default() -> Self {
+        trace!("default()");
+        Self::from(Inner::default())
+    }
+}
+
+impl<T: Clone> Clone for VoluntaryServitude<T> {
+    /// Creates new independent `VS` with clones of the current snapshot's elements (the append callback isn't copied)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1, 2];
+    /// let cloned = list.clone();
+    /// list.append(3);
+    /// assert_eq!(cloned, vs![1, 2]);
+    /// ```
+    #[inline]
+    fn clone(&self) -> Self {
+        debug!("clone()");
+        let iter = &mut self.iter();
+        let len = iter.len();
+        Self::from(iter.take(len).cloned().collect::<Inner<T>>())
+    }
+
+    /// Rebuilds chain from clones of `source`'s current snapshot (nodes can't be reused, but the chain is built directly)
+    ///
+    /// Iterators referencing the old chain will still work, the `Inner` allocation is only reused if nothing else references it
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let mut list = vs![1, 2, 3];
+    /// list.clone_from(&vs![4, 5]);
+    /// assert_eq!(list, vs![4, 5]);
+    /// ```
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        debug!("clone_from({:p})", source);
+        let iter = &mut source.iter();
+        let len = iter.len();
+        let cloned: Inner<T> = iter.take(len).cloned().collect();
+        let current = self.0.get_mut();
+        match Arc::get_mut(current) {
+            Some(inner) => *inner = cloned,
+            None => *current = Arc::new(cloned),
+        }
+        self.bump_generation();
+    }
+}
+
+impl<T: Debug> Debug for VoluntaryServitude<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("VoluntaryServitude")
+            .field(&self.iter().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Compares element-wise over a snapshot of each list, elements appended while comparing may be considered
+impl<T: PartialEq> PartialEq for VoluntaryServitude<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        trace!("eq({:p})", other);
+        self.iter().eq(&mut other.iter())
+    }
+}
+
+impl<T: Eq> Eq for VoluntaryServitude<T> {}
+
+/// Lexicographic comparison over a snapshot of each list (like slices, a prefix is smaller)
+///
+/// ```rust
+/// # use voluntary_servitude::vs;
+/// # env_logger::init();
+/// assert!(vs![1, 2] < vs![1, 2, 3]);
+/// assert!(vs![1, 3] > vs![1, 2, 3]);
+/// ```
+impl<T: PartialOrd> PartialOrd for VoluntaryServitude<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        trace!("partial_cmp({:p})", other);
+        self.iter().partial_cmp(&mut other.iter())
+    }
+}
+
+impl<T: Ord> Ord for VoluntaryServitude<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        trace!("cmp({:p})", other);
+        self.iter().cmp(&mut other.iter())
+    }
+}
+
+impl<T> Extend<T> for VoluntaryServitude<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        Self::extend(self, iter)
+    }
+}
+
+impl<'a, T: 'a + Copy> Extend<&'a T> for VoluntaryServitude<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        Self::extend(self, iter.into_iter().cloned())
+    }
+}
+
+/// Fallible iterators can be collected into `Result<VS<T>, E>`, stopping at the first `Err`
+///
+/// ```rust
+/// # use voluntary_servitude::VS;
+/// # env_logger::init();
+/// let list: Result<VS<u8>, &str> = vec![Ok(1), Ok(2)].into_iter().collect();
+/// assert_eq!(list.unwrap().iter().collect::<Vec<_>>(), vec![&1, &2]);
+///
+/// let list: Result<VS<u8>, &str> = vec![Ok(1), Err("bad"), Ok(3)].into_iter().collect();
+/// assert_eq!(list.unwrap_err(), "bad");
+/// ```
+impl<T> FromIterator<T> for VoluntaryServitude<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(Inner::from_iter(iter))
+    }
+}
+
+impl<'a, T: 'a + Copy> FromIterator<&'a T> for VoluntaryServitude<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = &'a T>>(iter: I) -> Self {
+        Self::from_iter(iter.into_iter().cloned())
+    }
+}
+
+/// Stores every element of the array in a single node
+///
+/// ```rust
+/// # use voluntary_servitude::{vs, VS};
+/// # env_logger::init();
+/// assert_eq!(VS::from([1, 2, 3]), vs![1, 2, 3]);
+/// assert!(VS::<u8>::from([]).is_empty());
+/// ```
+impl<T, const N: usize> From<[T; N]> for VoluntaryServitude<T> {
+    #[inline]
+    fn from(array: [T; N]) -> Self {
+        trace!("From<[T; {}]>", N);
+        let inner = Inner::default();
+        let _ = inner.append_values(Box::new(array));
+        Self::from(inner)
+    }
+}
+
+impl<T> From<Inner<T>> for VoluntaryServitude<T> {
+    #[inline]
+    fn from(inner: Inner<T>) -> Self {
+        trace!("From<Inner<T>>");
+        VoluntaryServitude(
+            RwLock::new(Arc::new(inner)),
+            AtomicUsize::new(0),
+            FillOnceAtomicOption::default(),
+        )
+    }
+}
+
+/// Exclusively owned elements of a [`VS`], obtained with [`try_into_unique`], that can be mutated without synchronization
+///
+/// [`VS`]: ./type.VS.html
+/// [`try_into_unique`]: ./struct.VoluntaryServitude.html#method.try_into_unique
+pub struct UniqueVS<T>(Inner<T>, usize, FillOnceAtomicOption<AppendCallback>);
+
+impl<T: Debug> Debug for UniqueVS<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("UniqueVS").field(&self.0).finish()
+    }
+}
+
+impl<T> UniqueVS<T> {
+    /// Returns number of elements
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let unique = vs![1, 2].try_into_unique().unwrap();
+    /// assert_eq!(unique.len(), 2);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Checks if there are no elements
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::{vs, VS};
+    /// # env_logger::init();
+    /// let unique = VS::<()>::new().try_into_unique().unwrap();
+    /// assert!(unique.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns mutable reference to the element at `index` (`None` if out of bounds)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let mut unique = vs![1, 2].try_into_unique().unwrap();
+    /// *unique.get_mut(1).unwrap() = 3;
+    /// assert_eq!(unique.get_mut(1), Some(&mut 3));
+    /// assert_eq!(unique.get_mut(2), None);
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        trace!("get_mut({})", index);
+        let value = self.0.get_mut(index);
+        if value.is_some() {
+            self.1 = self.1.wrapping_add(1);
+        }
+        value
+    }
+
+    /// Keeps only the elements that match `f`, which can also mutate them (relinks the chain in place, no clone needed)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let mut unique = vs![1, 2, 3, 4].try_into_unique().unwrap();
+    /// unique.retain_mut(|n| {
+    ///     *n *= 10;
+    ///     *n > 20
+    /// });
+    /// assert_eq!(unique.into_shared(), vs![30, 40]);
+    /// ```
+    #[inline]
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, f: F) {
+        debug!("retain_mut()");
+        self.0.retain_mut(f);
+        self.1 = self.1.wrapping_add(1);
+    }
+
+    /// Removes element at `index`, returning it (`None` if out of bounds)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let mut unique = vs![1, 2, 3].try_into_unique().unwrap();
+    /// assert_eq!(unique.remove_at(1), Some(2));
+    /// assert_eq!(unique.remove_at(2), None);
+    /// assert_eq!(unique.into_shared(), vs![1, 3]);
+    /// ```
+    #[inline]
+    pub fn remove_at(&mut self, index: usize) -> Option<T> {
+        trace!("remove_at({})", index);
+        let value = self.0.remove(index);
+        if value.is_some() {
+            self.1 = self.1.wrapping_add(1);
+        }
+        value
+    }
+
+    /// Removes element at `index` replacing it with the last element, returning it (`None` if out of bounds)
+    ///
+    /// Like `Vec::swap_remove` it doesn't preserve ordering, but it doesn't relink the middle of the chain
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let mut unique = vs![1, 2, 3, 4].try_into_unique().unwrap();
+    /// assert_eq!(unique.swap_remove_at(0), Some(1));
+    /// assert_eq!(unique.swap_remove_at(3), None);
+    /// assert_eq!(unique.into_shared(), vs![4, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn swap_remove_at(&mut self, index: usize) -> Option<T> {
+        trace!("swap_remove_at({})", index);
+        let last_index = self.len().checked_sub(1).filter(|&last| index <= last)?;
+        let last = self.pop()?;
+        if index == last_index {
+            return Some(last);
+        }
+        self.0.get_mut(index).map(|value| replace(value, last))
+    }
+
+    /// Removes last element, returning it (`None` if empty)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let mut unique = vs![1, 2].try_into_unique().unwrap();
+    /// assert_eq!(unique.pop(), Some(2));
+    /// unique.push(3);
+    /// assert_eq!(unique.pop(), Some(3));
+    /// assert_eq!(unique.pop(), Some(1));
+    /// assert_eq!(unique.pop(), None);
+    /// ```
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        trace!("pop()");
+        let last_index = self.len().checked_sub(1)?;
+        self.remove_at(last_index)
+    }
+
+    /// Inserts element after last node
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let mut unique = vs![1].try_into_unique().unwrap();
+    /// unique.push(2);
+    /// assert_eq!(unique.into_shared(), vs![1, 2]);
+    /// ```
     #[inline]
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        Self::from(Inner::from_iter(iter))
+    pub fn push(&mut self, value: T) {
+        self.0.push(value);
+        self.1 = self.1.wrapping_add(1);
+    }
+
+    /// Shares elements again as a `VS` (its generation accounts for the mutations done meanwhile)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::vs;
+    /// # env_logger::init();
+    /// let list = vs![1];
+    /// let generation = list.generation();
+    /// let mut unique = list.try_into_unique().unwrap();
+    /// unique.push(2);
+    /// assert_ne!(unique.into_shared().generation(), generation);
+    /// ```
+    #[inline]
+    pub fn into_shared(self) -> VoluntaryServitude<T> {
+        debug!("into_shared()");
+        let UniqueVS(inner, generation, callback) = self;
+        VoluntaryServitude(
+            RwLock::new(Arc::new(inner)),
+            AtomicUsize::new(generation),
+            callback,
+        )
+    }
+}
+
+impl<T> From<UniqueVS<T>> for VoluntaryServitude<T> {
+    #[inline]
+    fn from(unique: UniqueVS<T>) -> Self {
+        unique.into_shared()
+    }
+}
+
+/// Builds a [`VS`] locally with method chaining, splicing every value at once on `build`
+///
+/// No atomic operation happens while building, values are just buffered
+///
+/// ```rust
+/// # use voluntary_servitude::{vs, VSBuilder};
+/// # env_logger::init();
+/// let list = VSBuilder::new().push(1).extend(vec![2, 3]).push(4).build();
+/// assert_eq!(list, vs![1, 2, 3, 4]);
+/// ```
+///
+/// [`VS`]: ./type.VS.html
+#[derive(Clone, Debug)]
+pub struct VSBuilder<T>(Vec<T>);
+
+impl<T> Default for VSBuilder<T> {
+    #[inline]
+    fn default() -> Self {
+        trace!("default()");
+        VSBuilder(Vec::new())
+    }
+}
+
+impl<T> VSBuilder<T> {
+    /// Creates new empty builder
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::VSBuilder;
+    /// # env_logger::init();
+    /// let list = VSBuilder::<()>::new().build();
+    /// assert!(list.is_empty());
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        trace!("new()");
+        Self::default()
+    }
+
+    /// Buffers value to be inserted after the current ones
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::VSBuilder;
+    /// # env_logger::init();
+    /// let list = VSBuilder::new().push(1).push(2).build();
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2]);
+    /// ```
+    #[inline]
+    pub fn push(mut self, value: T) -> Self {
+        self.0.push(value);
+        self
+    }
+
+    /// Buffers every value in `iter` to be inserted after the current ones
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::VSBuilder;
+    /// # env_logger::init();
+    /// let list = VSBuilder::new().extend(0..3).build();
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&0, &1, &2]);
+    /// ```
+    #[inline]
+    pub fn extend<I: IntoIterator<Item = T>>(mut self, iter: I) -> Self {
+        self.0.extend(iter);
+        self
+    }
+
+    /// Creates `VS` with every buffered value (in order)
+    ///
+    /// ```rust
+    /// # use voluntary_servitude::VSBuilder;
+    /// # env_logger::init();
+    /// let builder = VSBuilder::new().push(3);
+    /// let (first, second) = (builder.clone().build(), builder.build());
+    /// assert_eq!(first, second);
+    /// ```
+    #[inline]
+    pub fn build(self) -> VoluntaryServitude<T> {
+        debug!("build() with {} values", self.0.len());
+        VoluntaryServitude::from_iter(self.0)
+    }
+}
+
+/// Concatenates every list in order, splicing their chains into a single new [`VS`]
+///
+/// Lists that aren't shared have their nodes moved, the others have their current snapshot cloned
+///
+/// [`VS`]: ./type.VS.html
+///
+/// ```rust
+/// # use voluntary_servitude::vs;
+/// # env_logger::init();
+/// use voluntary_servitude::concat;
+/// let shared = vs![3, 4];
+/// // The iterator keeps the chain shared, so it's cloned instead of moved
+/// let mut iter = shared.iter();
+/// let list = concat(vec![vs![1, 2], shared, vs![5]]);
+/// assert_eq!(list, vs![1, 2, 3, 4, 5]);
+/// assert_eq!(iter.collect::<Vec<_>>(), vec![&3, &4]);
+/// ```
+#[inline]
+pub fn concat<T, I>(lists: I) -> VoluntaryServitude<T>
+where
+    T: Clone,
+    I: IntoIterator<Item = VoluntaryServitude<T>>,
+{
+    debug!("concat()");
+    let concatenated = VoluntaryServitude::default();
+    for list in lists {
+        match list.try_into_unique() {
+            Ok(unique) => concatenated.append_inner(unique.0),
+            Err(shared) => {
+                let iter = &mut shared.iter();
+                let len = iter.len();
+                concatenated.append_inner(Inner::from_iter(iter.take(len).cloned()));
+            }
+        }
+    }
+    concatenated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::setup_logger;
+    use std::mem::drop;
+
+    #[test]
+    fn iter_outlives() {
+        setup_logger();
+        let vs = vs![1, 2, 3, 4];
+        let iter = vs.iter();
+        drop(vs);
+        drop(iter);
+    }
+
+    #[test]
+    fn voluntary_servitude_len_append_clear() {
+        setup_logger();
+        let list = vs![1, 2, 3];
+        assert_eq!(list.len(), 3);
+        list.append(4);
+        assert_eq!(list.len(), 4);
+        list.clear();
+        assert!(list.is_empty());
+        list.append(4);
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    fn extend_partial_eq() {
+        setup_logger();
+        let vs: VS<u8> = vs![1, 2, 3, 4, 5];
+        let iter = &mut vs.iter();
+        vs.extend(iter.cloned());
+        assert_eq!(
+            vs.iter().collect::<Vec<_>>(),
+            vec![&1u8, &2, &3, &4, &5, &1, &2, &3, &4, &5]
+        );
+    }
+
+    #[test]
+    fn swap_empty() {
+        let vs: VS<u8> = vs![1, 2, 3, 4, 5];
+        let old: VS<u8> = vs![5, 4, 3, 2, 1];
+        vs.swap(&old);
+        assert_eq!(vs.empty().collect::<Vec<_>>(), vec![&5, &4, &3, &2, &1]);
+        assert_eq!(old.empty().collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5]);
+        assert!(vs.is_empty());
+    }
+
+    #[test]
+    fn fold() {
+        setup_logger();
+        let vs = vs![1, 2, 3, 4];
+        assert_eq!(vs.fold(0, |acc, el| acc + el), 10);
+        assert_eq!(
+            vs.fold(String::new(), |acc, el| acc + &el.to_string()),
+            "1234"
+        );
+
+        let vs: VS<u8> = vs![];
+        assert_eq!(vs.fold(7, |acc, el| acc + el), 7);
+    }
+
+    #[test]
+    fn lexicographic_order() {
+        setup_logger();
+        assert!(vs![1, 2] < vs![1, 2, 3]);
+        assert!(vs![1, 3] > vs![1, 2]);
+        assert!(vs![1, 3] > vs![1, 2, 3]);
+        assert_eq!(vs![1, 2, 3], vs![1, 2, 3]);
+        assert_ne!(vs![1, 2, 3], vs![1, 2]);
+        assert_eq!(vs![1, 2].cmp(&vs![1, 2]), CmpOrdering::Equal);
+        assert_eq!(VS::<u8>::new().cmp(&vs![0]), CmpOrdering::Less);
+        assert_eq!(vs![1.0, 2.0].partial_cmp(&vs![1.0, f64::NAN]), None);
+
+        let mut lists = vec![vs![3], vs![1, 2, 3], vs![], vs![1, 2]];
+        lists.sort();
+        assert_eq!(lists, vec![vs![], vs![1, 2], vs![1, 2, 3], vs![3]]);
+    }
+
+    #[test]
+    fn get_mut() {
+        setup_logger();
+        let mut vs = vs![1, 2, 3];
+        *vs.get_mut(0).unwrap() += 10;
+        *vs.get_mut(2).unwrap() += 10;
+        assert_eq!(vs.iter().collect::<Vec<_>>(), vec![&11, &2, &13]);
+        assert_eq!(vs.get_mut(3), None);
+        assert_eq!(VS::<u8>::new().get_mut(0), None);
+    }
+
+    #[test]
+    fn get_mut_shared() {
+        setup_logger();
+        let mut vs = vs![1, 2, 3];
+        let iter = vs.iter();
+        assert_eq!(vs.get_mut(1), None);
+        assert_eq!(iter.len(), 3);
+        drop(iter);
+        assert_eq!(vs.get_mut(1), Some(&mut 2));
+    }
+
+    #[test]
+    fn first_last() {
+        setup_logger();
+        let vs: VS<u8> = vs![];
+        assert_eq!(vs.first(), None);
+        assert_eq!(vs.last(), None);
+
+        vs.append(1);
+        assert_eq!(vs.first(), Some(1));
+        assert_eq!(vs.last(), Some(1));
+
+        vs.extend(vec![2, 3, 4]);
+        assert_eq!(vs.first(), Some(1));
+        assert_eq!(vs.last(), Some(4));
+    }
+
+    #[test]
+    fn try_clear() {
+        setup_logger();
+        let vs = vs![1, 2, 3];
+        assert!(vs.try_clear());
+        assert!(vs.is_empty());
+
+        vs.append(1);
+        let lock = vs.0.read();
+        assert!(!vs.try_clear());
+        drop(lock);
+        assert_eq!(vs.len(), 1);
+    }
+
+    #[test]
+    fn extend_from_slice() {
+        setup_logger();
+        let vs: VS<u8> = vs![1];
+        vs.extend_from_slice(b"abc");
+        assert_eq!(vs.iter().collect::<Vec<_>>(), vec![&1, &b'a', &b'b', &b'c']);
+
+        vs.extend_from_slice(&[]);
+        assert_eq!(vs.len(), 4);
+        vs.append(2);
+        assert_eq!(vs.last(), Some(2));
+
+        let vs: VS<u8> = vs![];
+        vs.extend_from_slice(&[]);
+        assert!(vs.is_empty());
+        assert_eq!(vs.iter().last_node(), None);
+    }
+
+    #[test]
+    fn truncate() {
+        setup_logger();
+        let vs = vs![1, 2, 3, 4, 5];
+        vs.truncate(3);
+        assert_eq!(vs.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+        assert_eq!(vs.last(), Some(3));
+
+        vs.truncate(10);
+        assert_eq!(vs.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+
+        let iter = vs.iter();
+        vs.truncate(0);
+        assert!(vs.is_empty());
+        assert_eq!(vs.last(), None);
+        assert_eq!(iter.len(), 3);
+    }
+
+    #[test]
+    fn snapshot_arc() {
+        setup_logger();
+        let vs = vs![1, 2];
+        let first = vs.snapshot_arc();
+        vs.clear();
+        vs.append(3);
+        let second = vs.snapshot_arc();
+        vs.append(4);
+
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 2);
+        assert_eq!(Iter::from(first).collect::<Vec<_>>(), vec![&1, &2]);
+        assert_eq!(Iter::from(second).collect::<Vec<_>>(), vec![&3, &4]);
+    }
+
+    #[test]
+    fn generation() {
+        setup_logger();
+        let vs = vs![1, 2];
+        let mut generation = vs.generation();
+
+        let _ = (
+            vs.len(),
+            vs.is_empty(),
+            vs.first(),
+            vs.last(),
+            vs.iter().count(),
+        );
+        assert_eq!(vs.generation(), generation);
+
+        vs.append(3);
+        assert!(vs.generation() > generation);
+        generation = vs.generation();
+
+        vs.clear();
+        assert!(vs.generation() > generation);
+        generation = vs.generation();
+
+        vs.extend(vec![]);
+        assert_eq!(vs.generation(), generation);
+        vs.extend(vec![1]);
+        assert!(vs.generation() > generation);
+    }
+
+    #[test]
+    fn page() {
+        setup_logger();
+        let vs: VS<usize> = (0..10).collect();
+        assert_eq!(vs.page(0, 3), vec![0, 1, 2]);
+        assert_eq!(vs.page(3, 3), vec![3, 4, 5]);
+        assert_eq!(vs.page(9, 3), vec![9]);
+        assert!(vs.page(10, 3).is_empty());
+        assert!(vs.page(20, 3).is_empty());
+        assert!(vs.page(2, 0).is_empty());
+        assert!(VS::<usize>::new().page(0, 3).is_empty());
+    }
+
+    #[test]
+    fn retain_count() {
+        setup_logger();
+        let vs = vs![1, 2, 3, 4, 5];
+        let generation = vs.generation();
+        assert_eq!(vs.retain_count(|v| v % 2 == 0), 3);
+        assert_eq!(vs.iter().collect::<Vec<_>>(), vec![&2, &4]);
+        assert_ne!(vs.generation(), generation);
+
+        let generation = vs.generation();
+        assert_eq!(vs.retain_count(|_| true), 0);
+        assert_eq!(vs.generation(), generation);
+        assert_eq!(vs.retain_count(|_| false), 2);
+        assert!(vs.is_empty());
+    }
+
+    #[test]
+    fn retain_count_reentrant() {
+        setup_logger();
+        let vs: VS<_> = (0..10).collect();
+        let mut appended = false;
+        let removed = vs.retain_count(|v| {
+            // The lock isn't held while filtering, appends made meanwhile are filtered too
+            assert!(vs.len() >= 10);
+            if !appended {
+                appended = true;
+                vs.append(11);
+                vs.append(12);
+            }
+            v % 2 == 0
+        });
+        assert_eq!(removed, 6);
+        assert_eq!(vs, vs![0, 2, 4, 6, 8, 12]);
+
+        // Replaced while filtering, so it's left as is
+        assert_eq!(
+            vs.retain_count(|_| {
+                vs.clear();
+                false
+            }),
+            0
+        );
+        assert!(vs.is_empty());
+    }
+
+    #[test]
+    fn append_if_absent() {
+        setup_logger();
+        // Single-threaded only, concurrent callers may both insert the same value
+        let vs = vs![];
+        for value in [1, 2, 1, 3, 2, 3] {
+            let _ = vs.append_if_absent(value);
+        }
+        assert_eq!(vs.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+        assert!(!vs.append_if_absent(1));
+        assert!(vs.append_if_absent(4));
+        assert_eq!(vs.len(), 4);
+    }
+
+    #[test]
+    fn consolidate() {
+        setup_logger();
+        let (vs, other) = (vs![], vs![]);
+        for i in 0..100 {
+            vs.append(i);
+            other.append(i);
+        }
+        let iter = vs.iter();
+        let generation = vs.generation();
+        vs.consolidate();
+        assert_ne!(vs.generation(), generation);
+        assert_eq!(vs, other);
+        assert_eq!(vs.len(), 100);
+        // Every element is in a single node
+        let inner = vs.snapshot_arc();
+        assert_eq!(inner.first_node(), inner.last_node());
+        assert_eq!(vs.verify_integrity(), Ok(()));
+        assert_eq!(vs.last(), Some(99));
+        vs.append(100);
+        assert_eq!(vs.last(), Some(100));
+        assert_eq!(iter.len(), 100);
+
+        // A bulk node isn't split
+        let bulk = vs![];
+        bulk.extend_from_slice(&[1, 2, 3]);
+        bulk.consolidate();
+        let inner = bulk.snapshot_arc();
+        assert_eq!(inner.first_node(), inner.last_node());
+        assert_eq!(bulk, vs![1, 2, 3]);
+
+        let empty: VS<()> = vs![];
+        empty.consolidate();
+        assert!(empty.is_empty());
+        assert_eq!(empty.generation(), 0);
+    }
+
+    #[test]
+    fn builder() {
+        setup_logger();
+        let built = (0..1000).fold(VSBuilder::new(), |b, _| b.push(7)).build();
+        assert_eq!(built, vs![7; 1000]);
+        assert_eq!(built.len(), 1000);
+        assert_eq!(built.generation(), 0);
+
+        let built = VSBuilder::new().extend(0..998).push(998).push(999).build();
+        assert_eq!(built, (0..1000).collect());
+        assert!(VSBuilder::<()>::default().build().is_empty());
+    }
+
+    #[test]
+    fn append_size_overflow() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        let mut inner = Inner::default();
+        *inner.size.get_mut() = usize::MAX - 1;
+        assert_eq!(inner.append_len(1), usize::MAX);
+
+        // Nothing is linked when it would overflow
+        let err = catch_unwind(AssertUnwindSafe(|| inner.append_len(2))).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<&str>(),
+            Some(&"size of Inner overflowed usize")
+        );
+        assert_eq!(inner.len(), usize::MAX);
+        assert_eq!(inner.elements().collect::<Vec<_>>(), vec![&1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "size of Inner overflowed usize")]
+    fn push_size_overflow() {
+        let mut inner = Inner::default();
+        *inner.size.get_mut() = usize::MAX;
+        inner.push(1);
+    }
+
+    #[test]
+    fn iter_cloned() {
+        setup_logger();
+        let vs = vs![String::from("a"), String::from("b")];
+        let cloned = vs.iter_cloned();
+        vs.append(String::from("c"));
+        assert_eq!(cloned.collect::<Vec<_>>(), vec!["a", "b", "c"]);
+        assert_eq!(VS::<u8>::new().iter_cloned().count(), 0);
+    }
+
+    #[test]
+    fn approx_heap_bytes() {
+        setup_logger();
+        let vs: VS<u64> = vs![];
+        let empty = vs.approx_heap_bytes();
+        vs.extend(0..10);
+        let ten = vs.approx_heap_bytes();
+        vs.extend(10..20);
+        assert_eq!(vs.approx_heap_bytes() - ten, ten - empty);
+        assert_eq!(ten - empty, 10 * size_of::<Node<u64>>());
+        vs.clear();
+        assert_eq!(vs.approx_heap_bytes(), empty);
+    }
+
+    #[test]
+    fn try_into_unique() {
+        setup_logger();
+        let vs = vs![1, 2, 3];
+        let iter = vs.iter();
+        let vs = vs.try_into_unique().unwrap_err();
+        assert_eq!(vs, vs![1, 2, 3]);
+        drop(iter);
+
+        let snapshot = vs.snapshot_arc();
+        let vs = vs.try_into_unique().unwrap_err();
+        drop(snapshot);
+
+        let generation = vs.generation();
+        let mut unique = vs.try_into_unique().unwrap();
+        assert_eq!(unique.len(), 3);
+        *unique.get_mut(2).unwrap() = 4;
+        unique.push(5);
+        let vs = VS::from(unique);
+        assert_eq!(vs, vs![1, 2, 4, 5]);
+        assert_eq!(vs.generation(), generation + 2);
+        vs.append(6);
+        assert_eq!(vs.last(), Some(6));
+    }
+
+    #[test]
+    fn remove_at() {
+        setup_logger();
+        let mut unique = vs![1, 2, 3, 4, 5].try_into_unique().unwrap();
+        assert_eq!(unique.remove_at(0), Some(1));
+        assert_eq!(unique.remove_at(1), Some(3));
+        assert_eq!(unique.remove_at(2), Some(5));
+        assert_eq!(unique.remove_at(2), None);
+        assert_eq!(unique.len(), 2);
+
+        // Tail must be relinked so pushes keep working
+        unique.push(6);
+        let vs = unique.into_shared();
+        assert_eq!(vs, vs![2, 4, 6]);
+        vs.append(7);
+        assert_eq!(vs.iter().collect::<Vec<_>>(), vec![&2, &4, &6, &7]);
+
+        let mut unique = vs.try_into_unique().unwrap();
+        for value in 2..8 {
+            if value != 3 && value != 5 {
+                assert_eq!(unique.remove_at(0), Some(value));
+            }
+        }
+        assert!(unique.is_empty());
+        unique.push(8);
+        assert_eq!(unique.into_shared(), vs![8]);
     }
-}
 
-impl<'a, T: 'a + Copy> FromIterator<&'a T> for VoluntaryServitude<T> {
-    #[inline]
-    fn from_iter<I: IntoIterator<Item = &'a T>>(iter: I) -> Self {
-        Self::from_iter(iter.into_iter().cloned())
+    #[test]
+    fn swap_remove_at() {
+        setup_logger();
+        let mut unique = vs![1, 2, 3, 4, 5].try_into_unique().unwrap();
+        assert_eq!(unique.swap_remove_at(1), Some(2));
+        assert_eq!(unique.get_mut(1), Some(&mut 5));
+        assert_eq!(unique.swap_remove_at(3), Some(4));
+        assert_eq!(unique.swap_remove_at(3), None);
+        assert_eq!(unique.len(), 3);
+
+        // Tail must be relinked so pushes keep working
+        unique.push(6);
+        assert_eq!(unique.swap_remove_at(0), Some(1));
+        let vs = unique.into_shared();
+        assert_eq!(vs, vs![6, 5, 3]);
+        vs.append(7);
+        assert_eq!(vs.last(), Some(7));
+
+        let mut unique = vs.try_into_unique().unwrap();
+        while unique.swap_remove_at(0).is_some() {}
+        assert!(unique.is_empty());
+        assert_eq!(unique.swap_remove_at(0), None);
+        unique.push(8);
+        assert_eq!(unique.into_shared(), vs![8]);
+
+        let mut unique = VS::from([1, 2, 3]).try_into_unique().unwrap();
+        assert_eq!(unique.swap_remove_at(0), Some(1));
+        assert_eq!(unique.into_shared(), vs![3, 2]);
     }
-}
 
-impl<T> From<Inner<T>> for VoluntaryServitude<T> {
-    #[inline]
-    fn from(inner: Inner<T>) -> Self {
-        trace!("From<Inner<T>>");
-        VoluntaryServitude(RwLock::new(Arc::new(inner)))
+    #[test]
+    fn pop() {
+        setup_logger();
+        let mut unique = vs![1, 2, 3].try_into_unique().unwrap();
+        let _ = unique.0.append_values(Box::new([4, 5]));
+        for value in (1..6).rev() {
+            assert_eq!(unique.pop(), Some(value));
+            assert_eq!(unique.len(), value - 1);
+            assert_eq!(unique.0.verify_integrity(), Ok(()));
+            // `last_node` must point to the new tail
+            unique.push(10);
+            assert_eq!(unique.pop(), Some(10));
+        }
+        assert_eq!(unique.pop(), None);
+        assert!(unique.is_empty());
+
+        unique.push(6);
+        let vs = unique.into_shared();
+        vs.append(7);
+        assert_eq!(vs, vs![6, 7]);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::setup_logger;
-    use std::mem::drop;
+    #[test]
+    fn remove_at_drops() {
+        setup_logger();
+        let arc = Arc::new(());
+        let mut unique = vs![Arc::clone(&arc); 3].try_into_unique().unwrap();
+        drop(unique.remove_at(1));
+        assert_eq!(Arc::strong_count(&arc), 3);
+        drop(unique);
+        assert_eq!(Arc::strong_count(&arc), 1);
+    }
 
     #[test]
-    fn iter_outlives() {
+    fn rposition() {
+        setup_logger();
+        let vs = vs![1, 2, 3, 1, 2, 3];
+        assert_eq!(vs.rposition(|&v| v == 2), Some(4));
+        assert_eq!(vs.rposition(|&v| v == 3), Some(5));
+        assert_eq!(vs.rposition(|&v| v < 2), Some(3));
+        assert_eq!(vs![1, 2, 3].rposition(|&v| v == 1), Some(0));
+        assert_eq!(vs.rposition(|&v| v > 3), None);
+        assert_eq!(VS::<u8>::new().rposition(|_| true), None);
+    }
+
+    #[test]
+    fn write_to() {
+        setup_logger();
+        let vs: VS<u8> = (0..20_000).map(|i| (i % 251) as u8).collect();
+        let mut file = Vec::new();
+        assert_eq!(vs.write_to(&mut file).unwrap(), 20_000);
+        assert!(vs.iter().eq(file.iter()));
+
+        let mut file = Vec::new();
+        assert_eq!(VS::new().write_to(&mut file).unwrap(), 0);
+        assert!(file.is_empty());
+    }
+
+    #[test]
+    fn on_append() {
+        setup_logger();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let vs = vs![1];
+        vs.append(2);
+        let counter = Arc::clone(&calls);
+        let callback = move |_| {
+            let _ = counter.fetch_add(1, Ordering::Relaxed);
+        };
+        assert!(vs.on_append(Arc::new(callback)).is_ok());
+
+        for i in 0..10 {
+            vs.append(i);
+        }
+        assert_eq!(calls.load(Ordering::Relaxed), 10);
+        vs.extend(0..10);
+        vs.extend(0..0);
+        assert_eq!(calls.load(Ordering::Relaxed), 11);
+
+        // Callback survives exclusive ownership
+        let vs = vs.try_into_unique().unwrap().into_shared();
+        vs.append(0);
+        assert_eq!(calls.load(Ordering::Relaxed), 12);
+        drop(vs);
+        assert_eq!(Arc::strong_count(&calls), 1);
+    }
+
+    #[test]
+    fn binary_search() {
+        setup_logger();
+        let slice = [0, 1, 1, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55];
+        let vs: VS<i32> = slice.iter().collect();
+        for target in -1..60 {
+            match (vs.binary_search(&target), slice.binary_search(&target)) {
+                (Ok(index), Ok(_)) => assert_eq!(slice[index], target),
+                (found, expected) => assert_eq!(found, expected),
+            }
+        }
+        assert_eq!(vs.binary_search(&13), Ok(9));
+        assert_eq!(vs.binary_search(&4), Err(7));
+        assert_eq!(vs.binary_search(&100), Err(13));
+        assert!(matches!(vs.binary_search(&1), Ok(1..=4)));
+        assert_eq!(VS::new().binary_search(&1), Err(0));
+    }
+
+    #[test]
+    fn extend_from() {
+        setup_logger();
+        let (vs, other) = (vs![1, 2], vs![3, 4]);
+        vs.extend_from(&other);
+        assert_eq!(vs, vs![1, 2, 3, 4]);
+        assert_eq!(other, vs![3, 4]);
+
+        vs.extend_from(&vs);
+        assert_eq!(vs, vs![1, 2, 3, 4, 1, 2, 3, 4]);
+        vs.extend_from(&vs![]);
+        assert_eq!(vs.len(), 8);
+        vs.append(5);
+        assert_eq!(vs.last(), Some(5));
+    }
+
+    #[test]
+    fn sample_every() {
+        setup_logger();
+        let vs: VS<u32> = (0..10).collect();
+        assert_eq!(vs.sample_every(1), (0..10).collect::<Vec<_>>());
+        assert_eq!(vs.sample_every(2), vec![0, 2, 4, 6, 8]);
+        assert_eq!(vs.sample_every(3), vec![0, 3, 6, 9]);
+        assert_eq!(vs.sample_every(20), vec![0]);
+        assert!(VS::<u32>::new().sample_every(2).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "step must be greater than 0")]
+    fn sample_every_zero() {
+        let _ = vs![1].sample_every(0);
+    }
+
+    #[test]
+    fn bulk_nodes() {
+        setup_logger();
+        let vs = vs![1];
+        let mut iter = vs.iter();
+        vs.extend_from_slice(&[2, 3, 4]);
+        vs.append(5);
+        vs.extend_from_slice(&[6]);
+        vs.extend(7..9);
+        vs.extend_from_slice(&[9, 10]);
+        let expected: Vec<_> = (1..=10).collect();
+
+        assert_eq!((&mut iter).cloned().collect::<Vec<_>>(), expected);
+        assert_eq!(vs.iter_cloned().collect::<Vec<_>>(), expected);
+        assert_eq!(vs.len(), 10);
+        assert_eq!((vs.first(), vs.last()), (Some(1), Some(10)));
+        assert_eq!(vs.iter().last(), Some(&10));
+        assert_eq!(vs.page(2, 4), vec![3, 4, 5, 6]);
+        assert_eq!((&mut vs.iter()).position(|&v| v == 4), Some(3));
+        assert_eq!(vs.binary_search(&9), Ok(8));
+
+        let frozen = vs.freeze();
+        assert_eq!(
+            frozen.iter().rev().cloned().collect::<Vec<_>>(),
+            (1..=10).rev().collect::<Vec<_>>()
+        );
+        assert_eq!(frozen[2], 3);
+
+        let mut iter = vs.iter();
+        assert_eq!((&mut iter).nth(2), Some(&3));
+        assert_eq!(iter.index(), 3);
+        assert_eq!((&mut iter).next(), Some(&4));
+        assert_eq!((&mut iter).next(), Some(&5));
+
+        let mut follow = vs.follow();
+        assert_eq!((&mut follow).count(), 10);
+        vs.extend_from_slice(&[11, 12]);
+        assert_eq!((&mut follow).cloned().collect::<Vec<_>>(), vec![11, 12]);
+    }
+
+    #[test]
+    fn bulk_nodes_unique() {
         setup_logger();
+        let vs = vs![1];
+        vs.extend_from_slice(&[2, 3, 4]);
+        vs.append(5);
+        vs.extend_from_slice(&[6, 7]);
+
+        let mut unique = vs.try_into_unique().unwrap();
+        *unique.get_mut(2).unwrap() = 30;
+        *unique.get_mut(6).unwrap() = 70;
+        assert_eq!(unique.remove_at(1), Some(2));
+        assert_eq!(unique.remove_at(1), Some(30));
+        assert_eq!(unique.remove_at(1), Some(4));
+        assert_eq!(unique.remove_at(3), Some(70));
+        assert_eq!(unique.remove_at(2), Some(6));
+        assert_eq!(unique.remove_at(2), None);
+        assert_eq!(unique.len(), 2);
+
+        let vs = unique.into_shared();
+        assert_eq!(vs, vs![1, 5]);
+        vs.extend_from_slice(&[8]);
+        assert_eq!(vs.last(), Some(8));
+        assert_eq!(vs, vs![1, 5, 8]);
+    }
+
+    #[test]
+    fn collect_results() {
+        let ok: Result<VS<u8>, ()> = vec![Ok(1), Ok(2), Ok(3)].into_iter().collect();
+        assert_eq!(ok.unwrap().iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+
+        let mut consumed = 0;
+        let err: Result<VS<u8>, &str> = vec![Ok(1), Err("bad"), Ok(3), Err("worse")]
+            .into_iter()
+            .inspect(|_| consumed += 1)
+            .collect();
+        assert_eq!(err.unwrap_err(), "bad");
+        assert_eq!(consumed, 2);
+    }
+
+    #[test]
+    fn to_boxed_slice() {
+        let vs: VS<_> = (0..100).collect();
+        let boxed = vs.to_boxed_slice();
+        vs.append(100);
+        assert_eq!(boxed.len(), 100);
+        assert_eq!(
+            &*boxed,
+            &*(&mut vs.iter()).take(100).cloned().collect::<Vec<_>>()
+        );
+        assert!(VS::<u8>::default().to_boxed_slice().is_empty());
+    }
+
+    #[test]
+    fn partition() {
         let vs = vs![1, 2, 3, 4];
+        let (even, odd) = vs.partition(|n| n % 2 == 0);
+        assert_eq!(even.iter().collect::<Vec<_>>(), vec![&2, &4]);
+        assert_eq!(odd.iter().collect::<Vec<_>>(), vec![&1, &3]);
+        assert_eq!(vs.len(), 4);
+
+        let (all, none) = vs.partition(|_| true);
+        assert_eq!(all, vs);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn concat() {
+        setup_logger();
+        let shared = vs![4, 5];
+        let mut iter = shared.iter();
+        let lists = vec![vs![1, 2, 3], vs![], shared, vs![6]];
+        let list = super::concat(lists);
+        assert_eq!(
+            list.iter().collect::<Vec<_>>(),
+            vec![&1, &2, &3, &4, &5, &6]
+        );
+        assert_eq!(list.len(), 6);
+        list.append(7);
+        assert_eq!(list.last(), Some(7));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&4, &5]);
+        assert!(super::concat(Vec::<VS<u8>>::new()).is_empty());
+    }
+
+    #[test]
+    fn verify_integrity() {
+        setup_logger();
+        let vs: VS<_> = (0..10).collect();
+        vs.extend_from_slice(&[10, 11]);
+        assert_eq!(vs.verify_integrity(), Ok(()));
+        assert_eq!(VS::<u8>::default().verify_integrity(), Ok(()));
+
+        let inner = vs.snapshot_arc();
+        inner.size.store(13, Ordering::Relaxed);
+        let err = IntegrityError::TooShort {
+            size: 13,
+            counted: 12,
+        };
+        assert_eq!(vs.verify_integrity(), Err(err));
+
+        inner.size.store(11, Ordering::Relaxed);
+        assert_eq!(
+            vs.verify_integrity(),
+            Err(IntegrityError::TooLong { size: 11 })
+        );
+
+        inner.size.store(12, Ordering::Relaxed);
+        let last = inner.last_node.swap(null_mut(), Ordering::Relaxed);
+        assert_eq!(
+            vs.verify_integrity(),
+            Err(IntegrityError::LastNodeMismatch { nodes: 11 })
+        );
+        inner.last_node.store(last, Ordering::Relaxed);
+        assert_eq!(vs.verify_integrity(), Ok(()));
+    }
+
+    #[test]
+    fn unique_retain_mut() {
+        setup_logger();
+        let vs: VS<_> = (0..6).collect();
+        vs.extend_from_slice(&[6, 7, 8]);
+        let mut unique = vs.try_into_unique().unwrap();
+        unique.retain_mut(|n| {
+            *n *= 2;
+            *n % 4 == 0
+        });
+        assert_eq!(unique.len(), 5);
+        unique.push(100);
+        let vs = unique.into_shared();
+        assert_eq!(vs.verify_integrity(), Ok(()));
+        let expected = vec![&0, &4, &8, &12, &16, &100];
+        assert_eq!(vs.iter().collect::<Vec<_>>(), expected);
+
+        let mut unique = vs.try_into_unique().unwrap();
+        unique.retain_mut(|n| *n < 10);
+        unique.retain_mut(|n| *n != 0);
+        assert_eq!(unique.len(), 2);
+        unique.retain_mut(|_| false);
+        assert!(unique.is_empty());
+        unique.push(1);
+        let vs = unique.into_shared();
+        assert_eq!(vs.verify_integrity(), Ok(()));
+        assert_eq!(vs, vs![1]);
+    }
+
+    #[test]
+    fn from_array() {
+        setup_logger();
+        let vs = VS::from([1, 2, 3]);
+        assert_eq!(vs.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+        assert_eq!(vs.len(), 3);
+        vs.append(4);
+        assert_eq!(vs.verify_integrity(), Ok(()));
+        assert_eq!(vs, vs![1, 2, 3, 4]);
+
+        let empty = VS::<String>::from([]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.verify_integrity(), Ok(()));
+        empty.append("a".to_owned());
+        assert_eq!(empty.first(), Some("a".to_owned()));
+    }
+
+    #[test]
+    fn latest() {
+        setup_logger();
+        let log = VS::default();
+        assert_eq!(log.latest(&1), None::<String>);
+        log.append((1, "v1".to_owned()));
+        log.append((2, "other".to_owned()));
+        assert_eq!(log.latest(&1), Some("v1".to_owned()));
+        log.append((1, "v2".to_owned()));
+        assert_eq!(log.latest(&1), Some("v2".to_owned()));
+        assert_eq!(log.latest(&2), Some("other".to_owned()));
+        assert_eq!(log.latest(&3), None);
+    }
+
+    #[test]
+    fn outstanding_iters() {
+        setup_logger();
+        let vs = vs![1, 2, 3];
+        assert_eq!(vs.outstanding_iters(), 0);
+        let iters: Vec<_> = (0..3).map(|_| vs.iter()).collect();
+        let follow = vs.follow();
+        let weak = Arc::downgrade(&vs.snapshot_arc());
+        assert_eq!(vs.outstanding_iters(), 4);
+        drop(follow);
+        assert_eq!(vs.outstanding_iters(), 3);
+
+        vs.clear();
+        assert_eq!(vs.outstanding_iters(), 0);
+        assert_eq!(iters.iter().map(Iter::len).sum::<usize>(), 9);
+        drop(iters);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn drop_panic_frees_chain() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::sync::atomic::AtomicUsize;
+        setup_logger();
+
+        struct Bomb<'a>(usize, &'a AtomicUsize);
+        impl Drop for Bomb<'_> {
+            fn drop(&mut self) {
+                let _ = self.1.fetch_add(1, Ordering::Relaxed);
+                if self.0 % 3 == 1 {
+                    panic!("bomb {}", self.0);
+                }
+            }
+        }
+
+        let drops = AtomicUsize::new(0);
+        let vs: VS<_> = (0..10).map(|n| Bomb(n, &drops)).collect();
+        let err = catch_unwind(AssertUnwindSafe(move || drop(vs))).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<String>().map(String::as_str),
+            Some("bomb 1")
+        );
+        assert_eq!(drops.load(Ordering::Relaxed), 10);
+
+        // The first node's values are dropped after the chain, they can panic too
+        drops.store(0, Ordering::Relaxed);
+        let vs: VS<_> = (1..10).map(|n| Bomb(n, &drops)).collect();
+        let err = catch_unwind(AssertUnwindSafe(move || drop(vs))).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<String>().map(String::as_str),
+            Some("bomb 4")
+        );
+        assert_eq!(drops.load(Ordering::Relaxed), 9);
+    }
+
+    #[test]
+    fn extrema() {
+        setup_logger();
+        let vs: VS<(u8, char)> = vs![];
+        assert_eq!((vs.max_element(), vs.min_element()), (None, None));
+        assert_eq!(vs.max_by(|a, b| a.0.cmp(&b.0)), None);
+        assert_eq!(vs.min_by(|a, b| a.0.cmp(&b.0)), None);
+
+        vs.extend(vec![(2, 'a'), (5, 'b'), (1, 'c'), (5, 'd'), (1, 'e')]);
+        assert_eq!(
+            (vs.max_element(), vs.min_element()),
+            (Some((5, 'd')), Some((1, 'c')))
+        );
+        assert_eq!(vs.max_by(|a, b| a.0.cmp(&b.0)), Some((5, 'd')));
+        assert_eq!(vs.min_by(|a, b| a.0.cmp(&b.0)), Some((1, 'c')));
+        assert_eq!(vs.max_by(|a, b| b.1.cmp(&a.1)), Some((2, 'a')));
+    }
+
+    #[test]
+    fn chunks_owned() {
+        setup_logger();
+        let vs: VS<_> = (0..6).collect();
+        let chunks: Vec<_> = vs.chunks_owned(3).collect();
+        assert_eq!(chunks, vec![vec![0, 1, 2], vec![3, 4, 5]]);
+        let chunks: Vec<_> = vs.chunks_owned(4).collect();
+        assert_eq!(chunks, vec![vec![0, 1, 2, 3], vec![4, 5]]);
+        let chunks: Vec<_> = vs.chunks_owned(10).collect();
+        assert_eq!(chunks, vec![vec![0, 1, 2, 3, 4, 5]]);
+        assert_eq!(VS::<u8>::new().chunks_owned(2).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunks_owned size must be greater than 0")]
+    fn chunks_owned_zero() {
+        let _ = vs![1].chunks_owned(0);
+    }
+
+    #[test]
+    fn len_observes_appends() {
+        use std::thread::spawn;
+        setup_logger();
+        let vs = Arc::new(VS::default());
+        let producer = {
+            let vs = Arc::clone(&vs);
+            spawn(move || (0..1000).for_each(|n| vs.append(vec![n; 4])))
+        };
+
+        let mut seen = 0;
+        while seen < 1000 {
+            let len = vs.len();
+            // Every element counted must be fully written and linked
+            let mut iter = vs.iter();
+            for (index, value) in (&mut iter).take(len).enumerate() {
+                assert_eq!(value, &vec![index; 4]);
+            }
+            assert!(iter.index() >= len);
+            seen = len;
+        }
+        producer.join().unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    fn tracing_events() {
+        use std::sync::atomic::AtomicUsize;
+        use tracing::{span, Event, Metadata, Subscriber};
+
+        /// Counts events emitted by this crate
+        struct Counter(Arc<AtomicUsize>);
+        impl Subscriber for Counter {
+            fn enabled(&self, _: &Metadata) -> bool {
+                true
+            }
+            fn new_span(&self, _: &span::Attributes) -> span::Id {
+                span::Id::from_u64(1)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, event: &Event) {
+                if event.metadata().target().starts_with("voluntary_servitude") {
+                    let _ = self.0.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        let events = Arc::new(AtomicUsize::new(0));
+        tracing::subscriber::with_default(Counter(Arc::clone(&events)), || {
+            let vs = vs![1, 2];
+            vs.clear();
+        });
+        assert!(events.load(Ordering::Relaxed) > 0);
+    }
+
+    #[test]
+    fn clone_from() {
+        setup_logger();
+        let source: VS<_> = (0..10).map(|n| n.to_string()).collect();
+        let cloned = source.clone();
+        assert_eq!(cloned, source);
+        assert_eq!(cloned.verify_integrity(), Ok(()));
+        cloned.append("10".to_owned());
+        assert_eq!((source.len(), cloned.len()), (10, 11));
+
+        let mut target = vs!["a".to_owned(), "b".to_owned()];
+        let generation = target.generation();
+        let mut iter = target.iter();
+        target.clone_from(&source);
+        assert_eq!(target, source);
+        assert_ne!(target.generation(), generation);
+        assert_eq!((&mut iter).cloned().collect::<Vec<_>>(), vec!["a", "b"]);
+
+        // Without iterators the `Inner` is reused
+        target.clone_from(&vs![]);
+        assert!(target.is_empty());
+        target.clone_from(&cloned);
+        assert_eq!(target, cloned);
+        target.append("11".to_owned());
+        assert_eq!(target.verify_integrity(), Ok(()));
+        assert_eq!(target.len(), 12);
+    }
+
+    #[test]
+    fn keep_last() {
+        setup_logger();
+        let vs: VS<_> = (0..5).collect();
+        let generation = vs.generation();
+        vs.keep_last(10);
+        vs.keep_last(5);
+        assert_eq!((vs.len(), vs.generation()), (5, generation));
+
         let iter = vs.iter();
-        drop(vs);
-        drop(iter);
+        vs.keep_last(3);
+        assert_eq!(vs, vs![2, 3, 4]);
+        assert_ne!(vs.generation(), generation);
+        assert_eq!(iter.len(), 5);
+
+        vs.append(5);
+        assert_eq!(vs.verify_integrity(), Ok(()));
+        assert_eq!(vs, vs![2, 3, 4, 5]);
+        vs.keep_last(0);
+        assert!(vs.is_empty());
+        vs.append(6);
+        assert_eq!(vs, vs![6]);
     }
 
     #[test]
-    fn voluntary_servitude_len_append_clear() {
+    fn shared_threads() {
+        use std::thread::spawn;
         setup_logger();
-        let list = vs![1, 2, 3];
-        assert_eq!(list.len(), 3);
-        list.append(4);
-        assert_eq!(list.len(), 4);
-        list.clear();
-        assert!(list.is_empty());
-        list.append(4);
-        assert_eq!(list.len(), 1);
+        let vs = VS::shared_from_iter(vec![0; 10]);
+        let producers: Vec<_> = (1..5)
+            .map(|n| {
+                let vs = Arc::clone(&vs);
+                spawn(move || (0..1000).for_each(|_| vs.append(n)))
+            })
+            .collect();
+        producers.into_iter().for_each(|p| p.join().unwrap());
+        assert_eq!(vs.len(), 4010);
+        assert_eq!(vs.iter().sum::<usize>(), 1000 * (1 + 2 + 3 + 4));
+        assert!(VS::<u8>::shared().is_empty());
     }
 
     #[test]
-    fn extend_partial_eq() {
+    fn set_contents() {
         setup_logger();
-        let vs: VS<u8> = vs![1, 2, 3, 4, 5];
-        let iter = &mut vs.iter();
-        vs.extend(iter.cloned());
+        let vs = vs![1, 2, 3];
+        let other = vs![4, 5];
+        let (generation, other_generation) = (vs.generation(), other.generation());
+        let iter = vs.iter();
+        vs.set_contents(&other);
+        assert_eq!(vs, vs![4, 5]);
+        assert_eq!(other, vs![4, 5]);
+        assert_ne!(vs.generation(), generation);
+        assert_eq!(other.generation(), other_generation);
+        assert_eq!(iter.len(), 3);
+
+        // The chains are independent
+        vs.append(6);
+        other.append(7);
+        assert_eq!((vs.last(), other.last()), (Some(6), Some(7)));
+
+        vs.set_contents(&vs);
+        assert_eq!(vs, vs![4, 5, 6]);
+        vs.set_contents(&vs![]);
+        assert!(vs.is_empty());
+    }
+
+    #[test]
+    fn unique() {
+        setup_logger();
+        let vs = vs![1, 2, 1, 3, 2];
+        let generation = vs.generation();
+        vs.unique();
+        assert_eq!(vs, vs![1, 2, 3]);
+        assert_ne!(vs.generation(), generation);
+
+        let vs = vs![1, 2, 1, 3, 2];
+        let iter = vs.iter();
+        vs.unique_hashed();
+        assert_eq!(vs, vs![1, 2, 3]);
+        assert_eq!(iter.len(), 5);
+
+        // Nothing to remove keeps the same chain
+        let generation = vs.generation();
+        vs.unique();
+        vs.unique_hashed();
+        assert_eq!(vs.generation(), generation);
+        vs.append(1);
+        vs.unique_hashed();
+        assert_eq!(vs, vs![1, 2, 3]);
+
+        let empty: VS<String> = vs![];
+        empty.unique();
+        empty.unique_hashed();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn unique_hashed_many_duplicates() {
+        setup_logger();
+        let vs: VS<_> = (0..10_000).map(|i| (i * 7) % 100).collect();
+        // 7 and 100 are coprime, so the first 100 values are distinct and then they repeat
+        let expected: VS<_> = (0..100).map(|i| (i * 7) % 100).collect();
+        vs.unique_hashed();
+        assert_eq!(vs, expected);
+        assert_eq!(vs.len(), 100);
+        assert_eq!(vs.verify_integrity(), Ok(()));
+    }
+
+    #[test]
+    fn unique_reentrant() {
+        use std::sync::OnceLock;
+        static LIST: OnceLock<VS<Reentrant>> = OnceLock::new();
+
+        // Comparing and hashing read the list being deduplicated, which must not deadlock
+        #[derive(Clone, Debug, Eq)]
+        struct Reentrant(u8);
+        impl PartialEq for Reentrant {
+            fn eq(&self, other: &Self) -> bool {
+                LIST.get().map_or(0, VS::len) > 0 && self.0 == other.0
+            }
+        }
+        impl Hash for Reentrant {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                let _ = LIST.get().map(VS::len);
+                self.0.hash(state);
+            }
+        }
+
+        setup_logger();
+        let vs = LIST.get_or_init(|| [1, 2, 1, 3, 2].iter().map(|&n| Reentrant(n)).collect());
+        vs.unique();
+        assert_eq!(vs.iter().map(|r| r.0).collect::<Vec<_>>(), vec![1, 2, 3]);
+        vs.append(Reentrant(1));
+        vs.unique_hashed();
+        assert_eq!(vs.iter().map(|r| r.0).collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn extend_panic_unchanged() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        setup_logger();
+        let arc = Arc::new(());
+        let vs = vs![Arc::clone(&arc)];
+        let generation = vs.generation();
+        let iter = (0..10).map(|n| {
+            assert!(n < 5, "failed to build element");
+            Arc::clone(&arc)
+        });
+        assert!(catch_unwind(AssertUnwindSafe(|| vs.extend(iter))).is_err());
+
+        assert_eq!((vs.len(), vs.generation()), (1, generation));
+        assert_eq!(vs.verify_integrity(), Ok(()));
+        // The elements built before the panic were dropped
+        assert_eq!(Arc::strong_count(&arc), 2);
+        vs.append(Arc::clone(&arc));
+        assert_eq!(vs.len(), 2);
+    }
+
+    #[test]
+    fn iter_rev_cloned() {
+        setup_logger();
+        let vs: VS<_> = (0..100).map(|n| n.to_string()).collect();
+        let rev = vs.iter_rev_cloned();
+        vs.append("100".to_owned());
+        vs.clear();
+        let expected: Vec<_> = (0..100).rev().map(|n| n.to_string()).collect();
+        assert_eq!(rev.collect::<Vec<_>>(), expected);
+        assert_eq!(vs.iter_rev_cloned().count(), 0);
+    }
+
+    #[test]
+    fn flatten() {
+        setup_logger();
+        let shards = vs![vs![1, 2], vs![], vs![3, 4]];
+        let flat = shards.flatten();
+        assert_eq!(flat.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+        assert_eq!(flat.verify_integrity(), Ok(()));
+        // Shards are only read
+        assert_eq!(shards.len(), 3);
+        assert_eq!(VS::<VS<()>>::default().flatten().len(), 0);
+    }
+
+    #[test]
+    fn clone_into_vec() {
+        setup_logger();
+        let vs: VS<_> = (0..100).collect();
+        let mut buf = Vec::new();
+        vs.clone_into_vec(&mut buf);
+        assert_eq!(buf, (0..100).collect::<Vec<_>>());
+
+        let (capacity, ptr) = (buf.capacity(), buf.as_ptr());
+        vs.clear();
+        vs.extend(200..250);
+        vs.clone_into_vec(&mut buf);
+        assert_eq!(buf, (200..250).collect::<Vec<_>>());
+        // Fits, so the allocation is reused
+        assert_eq!((buf.capacity(), buf.as_ptr()), (capacity, ptr));
+
+        vs.clear();
+        vs.clone_into_vec(&mut buf);
+        assert!(buf.is_empty());
+        assert_eq!(buf.capacity(), capacity);
+    }
+
+    #[test]
+    fn try_extend() {
+        setup_logger();
+        let vs = vs![0];
+        assert_eq!(vs.try_extend((1..100).map(Ok::<_, ()>)), Ok(()));
+        assert!(vs.iter().eq((0..100).collect::<Vec<_>>().iter()));
+
+        let mut iter = (100..200).map(|n| if n == 150 { Err(n) } else { Ok(n) });
+        assert_eq!(vs.try_extend(&mut iter), Err(150));
+        // The prefix is committed and the rest isn't consumed
+        assert!(vs.iter().eq((0..150).collect::<Vec<_>>().iter()));
+        assert_eq!(iter.next(), Some(Ok(151)));
+        assert_eq!(vs.verify_integrity(), Ok(()));
+
         assert_eq!(
-            vs.iter().collect::<Vec<_>>(),
-            vec![&1u8, &2, &3, &4, &5, &1, &2, &3, &4, &5]
+            vs.try_extend(vec![Err("first"), Ok(1), Err("second")]),
+            Err("first")
         );
+        assert_eq!(vs.len(), 150);
+        vs.append(150);
+        assert_eq!(vs.last(), Some(150));
     }
 
     #[test]
-    fn swap_empty() {
-        let vs: VS<u8> = vs![1, 2, 3, 4, 5];
-        let mut old: VS<u8> = vs![5, 4, 3, 2, 1];
-        vs.swap(&mut old);
-        assert_eq!(vs.empty().collect::<Vec<_>>(), vec![&5, &4, &3, &2, &1]);
-        assert_eq!(old.empty().collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5]);
+    fn compact_tombstones() {
+        setup_logger();
+        let subscribers: Vec<_> = (0..10).map(Arc::new).collect();
+        let vs = vs![];
+        for subscriber in &subscribers {
+            vs.append_weak(subscriber);
+        }
+
+        let alive: Vec<_> = subscribers.into_iter().filter(|s| **s < 3).collect();
+        assert_eq!(vs.len(), 10);
+        vs.compact_tombstones();
+        assert_eq!(vs.len(), alive.len());
+        assert_eq!(vs.live_iter().collect::<Vec<_>>(), alive);
+
+        // Nothing to reclaim keeps the same chain
+        let generation = vs.generation();
+        vs.compact_tombstones();
+        assert_eq!(vs.generation(), generation);
+        assert_eq!(vs.len(), alive.len());
+
+        drop(alive);
+        vs.compact_tombstones();
         assert!(vs.is_empty());
+        assert_ne!(vs.generation(), generation);
     }
 
     #[test]
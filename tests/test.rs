@@ -1,6 +1,6 @@
-use voluntary_servitude::voluntary_servitude;
 use std::sync::{atomic::AtomicBool, atomic::AtomicUsize, atomic::Ordering, Arc};
 use std::{cmp::max, thread::spawn};
+use voluntary_servitude::voluntary_servitude;
 
 fn setup_logger() {
     use std::sync::Once;